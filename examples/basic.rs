@@ -5,6 +5,7 @@
 // Run: cargo run --example basic
 
 use auto_api_client::{Client, Error, OfferData, OffersParams};
+use futures_util::StreamExt;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -41,21 +42,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Pagination
-    if offers.meta.next_page > 0 {
-        let next_page = client
-            .get_offers(
-                source,
-                &OffersParams {
-                    page: offers.meta.next_page,
-                    brand: Some("Hyundai".into()),
-                    year_from: Some(2020),
-                    ..Default::default()
-                },
-            )
-            .await?;
-        println!("Next page has {} offers", next_page.result.len());
+    // Pagination: stream every offer across pages
+    let mut all_offers = client
+        .offers_stream(
+            source,
+            &OffersParams {
+                brand: Some("Hyundai".into()),
+                year_from: Some(2020),
+                ..Default::default()
+            },
+        )
+        .max_pages(3);
+    let mut total = 0;
+    while let Some(item) = all_offers.next().await {
+        item?;
+        total += 1;
     }
+    println!("Streamed {} offers from the first 3 pages", total);
 
     // --- Get single offer ---
 
@@ -126,6 +129,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }) => {
             println!("\nAuth error: {} (HTTP {})", message, status_code);
         }
+        Err(Error::RateLimited { retry_after, .. }) => {
+            println!("\nRate limited, retry after {:?}", retry_after);
+        }
         Err(Error::Api {
             status_code,
             message,
@@ -136,6 +142,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Err(Error::Network(e)) => {
             println!("\nNetwork error: {}", e);
         }
+        Err(e) => {
+            println!("\nError: {}", e);
+        }
     }
 
     Ok(())
@@ -0,0 +1,37 @@
+use reqwest::header::HeaderValue;
+
+use crate::error::Error;
+
+/// How the API key is sent with GET requests.
+///
+/// `get_offer_by_url` always uses the `x-api-key` header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuthMode {
+    /// As the `api_key` query parameter (default).
+    #[default]
+    Query,
+    /// As the `x-api-key` header, keeping the key out of access logs.
+    Header,
+    /// As both the query parameter and the header.
+    Both,
+}
+
+impl AuthMode {
+    pub(crate) fn uses_query(self) -> bool {
+        matches!(self, AuthMode::Query | AuthMode::Both)
+    }
+
+    pub(crate) fn uses_header(self) -> bool {
+        matches!(self, AuthMode::Header | AuthMode::Both)
+    }
+}
+
+/// Returns `key` as the `x-api-key` header value, marked sensitive so it
+/// stays out of logs.
+pub(crate) fn key_header(key: &str) -> Result<HeaderValue, Error> {
+    let mut value = HeaderValue::from_str(key).map_err(|_| {
+        Error::Credentials("API key contains characters not allowed in a header".to_string())
+    })?;
+    value.set_sensitive(true);
+    Ok(value)
+}
@@ -0,0 +1,242 @@
+//! Synchronous client for scripts and tools without an async runtime.
+//!
+//! Enable the `blocking` feature. Responses and errors are the same types
+//! the async [`Client`](crate::Client) returns:
+//!
+//! ```no_run
+//! use auto_api_client::OffersParams;
+//!
+//! # fn main() -> Result<(), auto_api_client::Error> {
+//! let client = auto_api_client::blocking::Client::new("your-api-key");
+//! let offers = client.get_offers("encar", &OffersParams {
+//!     page: 1,
+//!     brand: Some("BMW".into()),
+//!     ..Default::default()
+//! })?;
+//! println!("Got {} offers", offers.result.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use serde_json::Value;
+
+use crate::auth::{self, AuthMode};
+use crate::client::{parse_response, DEFAULT_API_VERSION, DEFAULT_BASE_URL, DEFAULT_TIMEOUT};
+use crate::credentials::{self, CredentialsProvider, StaticCredentials};
+use crate::error::Error;
+use crate::middleware::RawResponse;
+use crate::retry::{Attempts, RetryPolicy};
+use crate::source::Source;
+use crate::types::*;
+
+/// Blocking client for the auto-api.com car listings API.
+///
+/// Has the request methods of the async client. Rate limits, filter caching,
+/// strict params, local sorting, middleware and metrics are only available
+/// on the async client.
+///
+/// Like `reqwest::blocking::Client`, it must not be created or used inside
+/// an async runtime. Cloning is cheap: clones share the connection pool.
+#[derive(Clone)]
+pub struct Client {
+    credentials: Arc<dyn CredentialsProvider>,
+    auth_mode: AuthMode,
+    base_url: String,
+    api_version: String,
+    http_client: reqwest::blocking::Client,
+    retry_policy: RetryPolicy,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("credentials", &self.credentials)
+            .field("auth_mode", &self.auth_mode)
+            .field("base_url", &self.base_url)
+            .field("api_version", &self.api_version)
+            .field("retry_policy", &self.retry_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Client {
+    /// Creates a new blocking client with the given API key.
+    ///
+    /// Panics if the HTTP client can't be created; use
+    /// [`Client::with_credentials`] to handle that case as an error.
+    pub fn new(api_key: &str) -> Self {
+        Self::with_credentials(StaticCredentials::new(api_key)).expect("failed to build HTTP client")
+    }
+
+    /// Creates a client that asks `credentials` for the key before every request.
+    /// Returns `Error::Network` if the underlying HTTP client can't be created.
+    pub fn with_credentials(credentials: impl CredentialsProvider + 'static) -> Result<Self, Error> {
+        let http_client = reqwest::blocking::Client::builder()
+            .timeout(DEFAULT_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            credentials: Arc::new(credentials),
+            auth_mode: AuthMode::default(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_version: DEFAULT_API_VERSION.to_string(),
+            http_client,
+            retry_policy: RetryPolicy::default(),
+        })
+    }
+
+    /// Sets a custom base URL.
+    pub fn set_base_url(&mut self, base_url: &str) {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+    }
+
+    /// Sets a custom API version (default: "v2").
+    pub fn set_api_version(&mut self, version: &str) {
+        self.api_version = version.to_string();
+    }
+
+    /// Sets how the API key is sent with GET requests.
+    pub fn set_auth_mode(&mut self, mode: AuthMode) {
+        self.auth_mode = mode;
+    }
+
+    /// Replaces the retry policy.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Uses a caller-supplied `reqwest::blocking::Client`, e.g. for a proxy
+    /// or custom timeouts.
+    pub fn set_http_client(&mut self, http_client: reqwest::blocking::Client) {
+        self.http_client = http_client;
+    }
+
+    /// Returns available filters for a source (brands, models, body types, etc.)
+    pub fn get_filters(&self, source: impl Into<Source>) -> Result<Value, Error> {
+        let source = source.into();
+        let url = format!(
+            "{}/api/{}/{}/filters",
+            self.base_url, self.api_version, source
+        );
+        self.get(&url, &[])
+    }
+
+    /// Returns offers with pagination and filters.
+    /// `sort` and `order` are passed to the server; there is no local fallback.
+    pub fn get_offers(
+        &self,
+        source: impl Into<Source>,
+        params: &OffersParams,
+    ) -> Result<OffersResponse, Error> {
+        let source = source.into();
+        let url = format!(
+            "{}/api/{}/{}/offers",
+            self.base_url, self.api_version, source
+        );
+        let pairs = params.to_query_pairs();
+        let query: Vec<(&str, &str)> = pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        self.get(&url, &query)
+    }
+
+    /// Returns a single offer by inner_id.
+    pub fn get_offer(&self, source: impl Into<Source>, inner_id: &str) -> Result<OffersResponse, Error> {
+        let source = source.into();
+        let url = format!(
+            "{}/api/{}/{}/offer",
+            self.base_url, self.api_version, source
+        );
+        self.get(&url, &[("inner_id", inner_id)])
+    }
+
+    /// Returns a change_id for the given date (format: yyyy-mm-dd).
+    pub fn get_change_id(&self, source: impl Into<Source>, date: &str) -> Result<i64, Error> {
+        let source = source.into();
+        let url = format!(
+            "{}/api/{}/{}/change_id",
+            self.base_url, self.api_version, source
+        );
+        let result: ChangeIdResponse = self.get(&url, &[("date", date)])?;
+        Ok(result.change_id)
+    }
+
+    /// Returns a changes feed (added/changed/removed) starting from change_id.
+    pub fn get_changes(&self, source: impl Into<Source>, change_id: i64) -> Result<ChangesResponse, Error> {
+        let source = source.into();
+        let url = format!(
+            "{}/api/{}/{}/changes",
+            self.base_url, self.api_version, source
+        );
+        let change_id_str = change_id.to_string();
+        self.get(&url, &[("change_id", &change_id_str)])
+    }
+
+    /// Returns offer data by its URL on the marketplace.
+    /// Uses POST /api/v1/offer/info with x-api-key header.
+    pub fn get_offer_by_url(&self, offer_url: &str) -> Result<Value, Error> {
+        let url = format!("{}/api/v1/offer/info", self.base_url);
+
+        let mut body = HashMap::new();
+        body.insert("url", offer_url);
+
+        self.execute(|key| {
+            Ok(self
+                .http_client
+                .post(&url)
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .header("x-api-key", auth::key_header(key)?)
+                .json(&body))
+        })
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, url: &str, query: &[(&str, &str)]) -> Result<T, Error> {
+        self.execute(|key| {
+            let mut request = self.http_client.get(url).query(query);
+            if self.auth_mode.uses_query() {
+                request = request.query(&[("api_key", key)]);
+            }
+            if self.auth_mode.uses_header() {
+                request = request.header("x-api-key", auth::key_header(key)?);
+            }
+            Ok(request)
+        })
+    }
+
+    /// Sends the request built by `build` with the current API key, retrying
+    /// according to the retry policy.
+    fn execute<T, F>(&self, build: F) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+        F: Fn(&str) -> Result<reqwest::blocking::RequestBuilder, Error>,
+    {
+        let mut attempts = Attempts::new(&self.retry_policy);
+        loop {
+            let key = self.credentials.api_key()?;
+            let sent = send(build(&key)?);
+            if let Some(delay) = attempts.retry(&sent) {
+                std::thread::sleep(delay);
+                continue;
+            }
+            let result = sent.and_then(parse_response);
+            credentials::report_rejected(&*self.credentials, &key, &result);
+            return result;
+        }
+    }
+}
+
+fn send(request: reqwest::blocking::RequestBuilder) -> Result<RawResponse, Error> {
+    let response = request.send()?;
+    let url = response.url().clone();
+    let status = response.status().as_u16();
+    let headers = response.headers().clone();
+    let body = response.text()?;
+    Ok(RawResponse {
+        url,
+        status,
+        headers,
+        body,
+    })
+}
@@ -0,0 +1,360 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+
+use crate::auth::AuthMode;
+use crate::client::{
+    Client, DEFAULT_API_VERSION, DEFAULT_BASE_URL, DEFAULT_FILTERS_TTL, DEFAULT_LOCAL_SORT_MAX_PAGES,
+    DEFAULT_TIMEOUT,
+};
+use crate::credentials::{CredentialsProvider, StaticCredentials};
+use crate::error::Error;
+use crate::filters::{FiltersCache, Spawner};
+use crate::metrics::MetricsRecorder;
+use crate::middleware::Middleware;
+use crate::rate_limit::{Limits, RateLimit};
+use crate::retry::RetryPolicy;
+use crate::sort::{LocalSort, SortField};
+use crate::source::Source;
+
+/// Builder for [`Client`] with full control over the HTTP transport.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use auto_api_client::Client;
+///
+/// # fn main() -> Result<(), auto_api_client::Error> {
+/// let client = Client::builder("your-api-key")
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(60))
+///     .user_agent("my-sync-job/1.0")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ClientBuilder {
+    credentials: Arc<dyn CredentialsProvider>,
+    auth_mode: AuthMode,
+    base_url: String,
+    api_version: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    user_agent: Option<String>,
+    default_headers: HeaderMap,
+    pool_max_idle_per_host: Option<usize>,
+    http2_prior_knowledge: bool,
+    http_client: Option<reqwest::Client>,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<RateLimit>,
+    source_rate_limits: HashMap<String, RateLimit>,
+    strict_params: bool,
+    filters_ttl: Duration,
+    spawner: Option<Spawner>,
+    local_sort: HashSet<(String, SortField)>,
+    local_sort_max_pages: usize,
+    metrics: Option<Arc<dyn MetricsRecorder>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    #[cfg(feature = "tower")]
+    service: Option<crate::service::HttpService>,
+}
+
+impl ClientBuilder {
+    /// Creates a builder with the given API key and default settings.
+    pub fn new(api_key: &str) -> Self {
+        Self::with_credentials(StaticCredentials::new(api_key))
+    }
+
+    /// Creates a builder that asks `credentials` for the key before every request.
+    ///
+    /// ```no_run
+    /// use auto_api_client::{ClientBuilder, KeyPool};
+    ///
+    /// # fn main() -> Result<(), auto_api_client::Error> {
+    /// let client = ClientBuilder::with_credentials(KeyPool::new(["key-a", "key-b"])).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_credentials(credentials: impl CredentialsProvider + 'static) -> Self {
+        Self {
+            credentials: Arc::new(credentials),
+            auth_mode: AuthMode::default(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_version: DEFAULT_API_VERSION.to_string(),
+            timeout: Some(DEFAULT_TIMEOUT),
+            connect_timeout: None,
+            read_timeout: None,
+            proxy: None,
+            user_agent: None,
+            default_headers: HeaderMap::new(),
+            pool_max_idle_per_host: None,
+            http2_prior_knowledge: false,
+            http_client: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+            source_rate_limits: HashMap::new(),
+            strict_params: false,
+            filters_ttl: DEFAULT_FILTERS_TTL,
+            spawner: None,
+            local_sort: HashSet::new(),
+            local_sort_max_pages: DEFAULT_LOCAL_SORT_MAX_PAGES,
+            metrics: None,
+            middleware: Vec::new(),
+            #[cfg(feature = "tower")]
+            service: None,
+        }
+    }
+
+    /// Sets a custom base URL (default: `https://api1.auto-api.com`).
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets a custom API version (default: "v2").
+    pub fn api_version(mut self, version: &str) -> Self {
+        self.api_version = version.to_string();
+        self
+    }
+
+    /// Sets how the API key is sent with GET requests (default: query parameter).
+    pub fn auth_mode(mut self, mode: AuthMode) -> Self {
+        self.auth_mode = mode;
+        self
+    }
+
+    /// Sets the total request timeout (default: 30 seconds).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Disables the total request timeout.
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for each read from the response body.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Routes all requests through the given proxy.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Sets headers sent with every request.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept per host.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Forces HTTP/2 for every connection, skipping negotiation. Requests fail
+    /// against servers that only speak HTTP/1.1.
+    ///
+    /// Without this, HTTPS connections already use HTTP/2 when the server
+    /// offers it during the TLS handshake (ALPN), and fall back to HTTP/1.1.
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Uses a caller-supplied `reqwest::Client`.
+    /// All transport settings on this builder are ignored in that case.
+    pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Sets the retry policy used by every request (default: a single attempt).
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Sets the request limits shared by all sources without their own limit.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// Sets separate request limits for one source.
+    pub fn source_rate_limit(mut self, source: impl Into<Source>, limit: RateLimit) -> Self {
+        self.source_rate_limits
+            .insert(source.into().as_str().to_string(), limit);
+        self
+    }
+
+    /// Validates `OffersParams` against the source's cached filters before
+    /// `get_offers` sends a request (default: false).
+    pub fn strict_params(mut self, strict: bool) -> Self {
+        self.strict_params = strict;
+        self
+    }
+
+    /// Sets how long filters are cached per source (default: 1 hour).
+    /// `Duration::ZERO` disables the cache.
+    pub fn filters_ttl(mut self, ttl: Duration) -> Self {
+        self.filters_ttl = ttl;
+        self
+    }
+
+    /// Sets the function used to run background work, such as refreshing
+    /// expired filters while the stale ones are still served.
+    ///
+    /// Without a spawner, expired filters are reloaded before returning.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), auto_api_client::Error> {
+    /// let client = auto_api_client::Client::builder("your-api-key")
+    ///     .spawner(|future| {
+    ///         tokio::spawn(future);
+    ///     })
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawner<F>(mut self, spawn: F) -> Self
+    where
+        F: Fn(Pin<Box<dyn Future<Output = ()> + Send>>) + Send + Sync + 'static,
+    {
+        self.spawner = Some(Spawner(Arc::new(spawn)));
+        self
+    }
+
+    /// Always sorts offers of `source` by `field` locally instead of asking
+    /// the server first.
+    pub fn local_sort(mut self, source: impl Into<Source>, field: SortField) -> Self {
+        self.local_sort
+            .insert((source.into().as_str().to_string(), field));
+        self
+    }
+
+    /// Sets how many pages may be fetched to sort a search locally (default: 20).
+    /// Larger result sets fail with `Error::InvalidParams`.
+    pub fn local_sort_max_pages(mut self, max: usize) -> Self {
+        self.local_sort_max_pages = max;
+        self
+    }
+
+    /// Reports request counts, latency, bytes received and page sizes to `recorder`.
+    /// See [`PrometheusRecorder`](crate::PrometheusRecorder) for a ready-made one.
+    pub fn metrics(mut self, recorder: impl MetricsRecorder + 'static) -> Self {
+        self.metrics = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Adds `middleware` to the end of the chain run around every request.
+    /// See [`Middleware`] for the order hooks are called in.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Sends requests through a `tower::Service` instead of the built-in
+    /// reqwest client, so your own layers (timeouts, load shedding, buffering)
+    /// apply to every API call. Wrap [`ReqwestService`](crate::ReqwestService)
+    /// to do the actual HTTP.
+    ///
+    /// Transport settings on this builder, such as timeouts and proxy, are
+    /// ignored by the service.
+    #[cfg(feature = "tower")]
+    pub fn tower_service<S>(mut self, service: S) -> Self
+    where
+        S: tower::Service<http::Request<Vec<u8>>, Response = http::Response<Vec<u8>>>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        S::Future: Send + 'static,
+    {
+        self.service = Some(crate::service::HttpService::new(service));
+        self
+    }
+
+    /// Builds the client.
+    /// Returns `Error::Network` if the underlying HTTP client can't be created.
+    pub fn build(self) -> Result<Client, Error> {
+        let http_client = match self.http_client {
+            Some(http_client) => http_client,
+            None => {
+                let mut builder =
+                    reqwest::Client::builder().default_headers(self.default_headers);
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.read_timeout {
+                    builder = builder.read_timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(max) = self.pool_max_idle_per_host {
+                    builder = builder.pool_max_idle_per_host(max);
+                }
+                if self.http2_prior_knowledge {
+                    builder = builder.http2_prior_knowledge();
+                }
+
+                builder.build()?
+            }
+        };
+
+        Ok(Client {
+            credentials: self.credentials,
+            auth_mode: self.auth_mode,
+            base_url: self.base_url,
+            api_version: self.api_version,
+            http_client,
+            retry_policy: self.retry_policy,
+            limits: Arc::new(Limits::new(
+                self.rate_limit.as_ref(),
+                &self.source_rate_limits,
+            )),
+            strict_params: self.strict_params,
+            filters_cache: Arc::new(FiltersCache::new(self.filters_ttl)),
+            spawner: self.spawner,
+            local_sort: Arc::new(LocalSort::new(self.local_sort_max_pages, self.local_sort)),
+            metrics: self.metrics,
+            middleware: self.middleware.into(),
+            #[cfg(feature = "tower")]
+            service: self.service,
+        })
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::Error;
+use crate::stream::ChangeCursor;
+
+/// Persists changes-feed cursors per source, so consumers can resume after a restart.
+///
+/// Used by [`Client::sync_changes`](crate::Client::sync_changes), which saves
+/// the cursor after each processed batch.
+pub trait CheckpointStore: Send + Sync {
+    /// Returns the saved cursor for `source`, if any.
+    fn load(&self, source: &str) -> Result<Option<ChangeCursor>, Error>;
+
+    /// Saves the cursor for `source`, replacing the previous one.
+    fn save(&self, source: &str, cursor: ChangeCursor) -> Result<(), Error>;
+}
+
+impl<S: CheckpointStore + ?Sized> CheckpointStore for &S {
+    fn load(&self, source: &str) -> Result<Option<ChangeCursor>, Error> {
+        (**self).load(source)
+    }
+
+    fn save(&self, source: &str, cursor: ChangeCursor) -> Result<(), Error> {
+        (**self).save(source, cursor)
+    }
+}
+
+/// Checkpoint store kept in memory. Cursors are lost when the process exits.
+#[derive(Debug, Default)]
+pub struct MemoryCheckpointStore {
+    cursors: Mutex<HashMap<String, ChangeCursor>>,
+}
+
+impl MemoryCheckpointStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CheckpointStore for MemoryCheckpointStore {
+    fn load(&self, source: &str) -> Result<Option<ChangeCursor>, Error> {
+        let cursors = self.cursors.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(cursors.get(source).copied())
+    }
+
+    fn save(&self, source: &str, cursor: ChangeCursor) -> Result<(), Error> {
+        let mut cursors = self.cursors.lock().unwrap_or_else(|e| e.into_inner());
+        cursors.insert(source.to_string(), cursor);
+        Ok(())
+    }
+}
+
+/// Checkpoint store backed by a JSON file mapping source names to cursors.
+///
+/// Writes go to a temporary file that is flushed to disk and then renamed
+/// over the original, so a crash never leaves a half-written file behind.
+#[derive(Debug)]
+pub struct JsonFileCheckpointStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonFileCheckpointStore {
+    /// Creates a store at `path`. The file is created on the first save.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn read_all(&self) -> Result<HashMap<String, ChangeCursor>, Error> {
+        match fs::read(&self.path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| Error::Checkpoint(e.into())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(Error::Checkpoint(e.into())),
+        }
+    }
+}
+
+impl CheckpointStore for JsonFileCheckpointStore {
+    fn load(&self, source: &str) -> Result<Option<ChangeCursor>, Error> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read_all()?.get(source).copied())
+    }
+
+    fn save(&self, source: &str, cursor: ChangeCursor) -> Result<(), Error> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut cursors = self.read_all()?;
+        cursors.insert(source.to_string(), cursor);
+
+        let json = serde_json::to_vec_pretty(&cursors).map_err(|e| Error::Checkpoint(e.into()))?;
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        replace_file(&self.path, &tmp, &json).map_err(|e| Error::Checkpoint(e.into()))
+    }
+}
+
+/// Writes `contents` to `tmp`, syncs it and renames it over `path`, then
+/// syncs the directory so the rename itself survives a crash.
+fn replace_file(path: &Path, tmp: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = fs::File::create(tmp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(tmp, path)?;
+
+    // Windows can't open a directory to sync it.
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Checkpoint store backed by a SQLite table named `auto_api_checkpoints`.
+#[cfg(feature = "sqlite")]
+pub struct SqliteCheckpointStore {
+    conn: Mutex<rusqlite::Connection>,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteCheckpointStore {
+    /// Opens (or creates) the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let conn = rusqlite::Connection::open(path).map_err(|e| Error::Checkpoint(e.into()))?;
+        Self::from_connection(conn)
+    }
+
+    /// Uses an existing connection, creating the table if needed.
+    pub fn from_connection(conn: rusqlite::Connection) -> Result<Self, Error> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS auto_api_checkpoints (
+                source TEXT PRIMARY KEY,
+                change_id INTEGER NOT NULL,
+                offset INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| Error::Checkpoint(e.into()))?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl CheckpointStore for SqliteCheckpointStore {
+    fn load(&self, source: &str) -> Result<Option<ChangeCursor>, Error> {
+        use rusqlite::OptionalExtension;
+
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.query_row(
+            "SELECT change_id, offset FROM auto_api_checkpoints WHERE source = ?1",
+            [source],
+            |row| {
+                Ok(ChangeCursor {
+                    change_id: row.get(0)?,
+                    offset: row.get::<_, i64>(1)? as usize,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| Error::Checkpoint(e.into()))
+    }
+
+    fn save(&self, source: &str, cursor: ChangeCursor) -> Result<(), Error> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.execute(
+            "INSERT INTO auto_api_checkpoints (source, change_id, offset) VALUES (?1, ?2, ?3)
+             ON CONFLICT(source) DO UPDATE SET change_id = excluded.change_id, offset = excluded.offset",
+            rusqlite::params![source, cursor.change_id, cursor.offset as i64],
+        )
+        .map(|_| ())
+        .map_err(|e| Error::Checkpoint(e.into()))
+    }
+}
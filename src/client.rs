@@ -1,32 +1,85 @@
 use std::collections::HashMap;
-use std::time::Duration;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
 use serde_json::Value;
 
-use crate::error::Error;
+use crate::auth::{self, AuthMode};
+use crate::builder::ClientBuilder;
+use crate::checkpoint::CheckpointStore;
+use crate::credentials::{self, CredentialsProvider};
+use crate::error::{Error, ParamError};
+use crate::filters::{Cached, Filters, FiltersCache, Spawner};
+use crate::metrics::{MetricsRecorder, Outcome, RequestMetrics};
+use crate::middleware::{self, Middleware, RawResponse};
+use crate::rate_limit::Limits;
+use crate::retry::{self, Attempts, RetryPolicy};
+use crate::sort::{self, LocalSort, SortField, SortedBy};
+use crate::source::Source;
+use crate::stream::{ChangeCursor, ChangesStart, ChangesStream, OffersStream};
+use crate::telemetry::{self, Span};
 use crate::types::*;
 
+pub(crate) const DEFAULT_BASE_URL: &str = "https://api1.auto-api.com";
+pub(crate) const DEFAULT_API_VERSION: &str = "v2";
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+pub(crate) const DEFAULT_FILTERS_TTL: Duration = Duration::from_secs(3600);
+pub(crate) const DEFAULT_LOCAL_SORT_MAX_PAGES: usize = 20;
+
 /// Client for the auto-api.com car listings API.
+///
+/// Cloning is cheap: clones share the underlying connection pool.
+/// `Debug` output never includes the API key.
+#[derive(Clone)]
 pub struct Client {
-    api_key: String,
-    base_url: String,
-    api_version: String,
-    http_client: reqwest::Client,
+    pub(crate) credentials: Arc<dyn CredentialsProvider>,
+    pub(crate) auth_mode: AuthMode,
+    pub(crate) base_url: String,
+    pub(crate) api_version: String,
+    pub(crate) http_client: reqwest::Client,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) limits: Arc<Limits>,
+    pub(crate) strict_params: bool,
+    pub(crate) filters_cache: Arc<FiltersCache>,
+    pub(crate) spawner: Option<Spawner>,
+    pub(crate) local_sort: Arc<LocalSort>,
+    pub(crate) metrics: Option<Arc<dyn MetricsRecorder>>,
+    pub(crate) middleware: Arc<[Arc<dyn Middleware>]>,
+    #[cfg(feature = "tower")]
+    pub(crate) service: Option<crate::service::HttpService>,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("credentials", &self.credentials)
+            .field("auth_mode", &self.auth_mode)
+            .field("base_url", &self.base_url)
+            .field("api_version", &self.api_version)
+            .field("retry_policy", &self.retry_policy)
+            .field("strict_params", &self.strict_params)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Client {
     /// Creates a new API client with the given API key.
+    ///
+    /// Panics if the HTTP client can't be created; use [`Client::builder`]
+    /// to handle that case as an error.
     pub fn new(api_key: &str) -> Self {
-        Self {
-            api_key: api_key.to_string(),
-            base_url: "https://api1.auto-api.com".to_string(),
-            api_version: "v2".to_string(),
-            http_client: reqwest::Client::builder()
-                .timeout(Duration::from_secs(30))
-                .build()
-                .expect("failed to build HTTP client"),
-        }
+        ClientBuilder::new(api_key)
+            .build()
+            .expect("failed to build HTTP client")
+    }
+
+    /// Returns a builder for configuring timeouts, proxy, headers and more.
+    /// Use [`ClientBuilder::with_credentials`] for rotating keys.
+    pub fn builder(api_key: &str) -> ClientBuilder {
+        ClientBuilder::new(api_key)
     }
 
     /// Sets a custom base URL.
@@ -39,65 +92,338 @@ impl Client {
         self.api_version = version.to_string();
     }
 
+    /// Sets how the API key is sent with GET requests (default: query parameter).
+    pub fn set_auth_mode(&mut self, mode: AuthMode) {
+        self.auth_mode = mode;
+    }
+
+    /// Sets the retry policy used by every request.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Enables validation of `OffersParams` against the source's filters
+    /// before `get_offers` sends a request (default: off).
+    /// Filters are fetched once per source and cached.
+    pub fn set_strict_params(&mut self, strict: bool) {
+        self.strict_params = strict;
+    }
+
+    /// Returns a copy of this client that uses `policy` instead of the
+    /// configured retry policy, e.g. for a single call:
+    /// `client.with_retry_policy(policy).get_offers(...)`.
+    pub fn with_retry_policy(&self, policy: RetryPolicy) -> Self {
+        let mut client = self.clone();
+        client.retry_policy = policy;
+        client
+    }
+
     /// Returns available filters for a source (brands, models, body types, etc.)
-    pub async fn get_filters(&self, source: &str) -> Result<Value, Error> {
-        let url = format!(
-            "{}/api/{}/{}/filters",
-            self.base_url, self.api_version, source
-        );
-        self.get(&url, &[]).await
+    ///
+    /// Responses are cached per source for the configured TTL (default: 1 hour).
+    /// Once expired, the cached value is still returned while a background
+    /// refresh runs if a spawner is configured; otherwise it is reloaded first.
+    pub async fn get_filters(&self, source: impl Into<Source>) -> Result<Value, Error> {
+        let source = source.into();
+        Ok(self.cached_filters(&source).await?.raw.clone())
+    }
+
+    /// Returns available filters for a source as a navigable [`Filters`] catalogue.
+    /// The raw response is kept in `Filters.raw`. Cached like [`Client::get_filters`].
+    pub async fn get_filters_typed(&self, source: impl Into<Source>) -> Result<Filters, Error> {
+        let source = source.into();
+        Ok(Filters::clone(&*self.cached_filters(&source).await?))
+    }
+
+    /// Drops the cached filters of a source, so the next call reloads them.
+    pub fn invalidate_filters(&self, source: impl Into<Source>) {
+        self.filters_cache.remove(source.into().as_str());
     }
 
     /// Returns a paginated list of offers with optional filters.
+    ///
+    /// With `params.sort` set, the first search by an ordering checks that the
+    /// page, together with the page after it, comes back in order. If not, the
+    /// server doesn't support that ordering for the source. From then on the
+    /// client fetches the full result set (up to the configured page limit),
+    /// sorts and pages it locally, and sets `sorted_locally` on the response.
+    /// Every such call fetches the set again; use [`Client::offers_stream`] to
+    /// walk all pages with a single fetch.
     pub async fn get_offers(
         &self,
-        source: &str,
+        source: impl Into<Source>,
+        params: &OffersParams,
+    ) -> Result<OffersResponse, Error> {
+        self.offer_pages(&source.into(), params, 1).await
+    }
+
+    /// Like [`Client::get_offers`], but a locally sorted response holds up to
+    /// `pages` pages starting at `params.page`.
+    pub(crate) async fn offer_pages(
+        &self,
+        source: &Source,
         params: &OffersParams,
+        pages: usize,
     ) -> Result<OffersResponse, Error> {
+        if self.strict_params {
+            params.validate(&*self.cached_filters(source).await?)?;
+        }
+
+        let response = self.sorted_offers(source, params, pages).await?;
+        self.record_items(source, "offers", response.result.len());
+        Ok(response)
+    }
+
+    async fn sorted_offers(
+        &self,
+        source: &Source,
+        params: &OffersParams,
+        pages: usize,
+    ) -> Result<OffersResponse, Error> {
+        let Some(field) = params.sort else {
+            return self.fetch_offers(source, params).await;
+        };
+        match self.local_sort.get(source, field) {
+            Some(SortedBy::Server) => return self.fetch_offers(source, params).await,
+            Some(SortedBy::Client) => {}
+            None => {
+                let response = self.fetch_offers(source, params).await?;
+                match self.server_sorts(source, params, field, &response).await? {
+                    None => return Ok(response),
+                    Some(true) => {
+                        self.local_sort.insert(source, field, SortedBy::Server);
+                        return Ok(response);
+                    }
+                    Some(false) => self.local_sort.insert(source, field, SortedBy::Client),
+                }
+            }
+        }
+        self.sort_offers_locally(source, params, field, pages).await
+    }
+
+    /// Checks whether the server honoured the ordering of `response`. A single
+    /// page can be in order by chance, so it's checked together with the next
+    /// (or previous) page. Offers without a sort key are skipped. `None` if it
+    /// can't tell: the result set is a single page, or fewer than two offers
+    /// have a key; the ordering is then checked again on the next search.
+    async fn server_sorts(
+        &self,
+        source: &Source,
+        params: &OffersParams,
+        field: SortField,
+        response: &OffersResponse,
+    ) -> Result<Option<bool>, Error> {
+        let order = params.order.unwrap_or_default();
+        if sort::is_ordered(&response.result, field, order) == Some(false) {
+            return Ok(Some(false));
+        }
+
+        let page = params.page.max(1);
+        let has_next = response.meta.next_page > 0 && !response.result.is_empty();
+        let neighbour = if has_next {
+            response.meta.next_page
+        } else if page > 1 {
+            page - 1
+        } else {
+            return Ok(None);
+        };
+        let other = self
+            .fetch_offers(source, &OffersParams { page: neighbour, ..params.clone() })
+            .await?;
+        Ok(if has_next {
+            sort::is_ordered(response.result.iter().chain(&other.result), field, order)
+        } else {
+            sort::is_ordered(other.result.iter().chain(&response.result), field, order)
+        })
+    }
+
+    async fn fetch_offers(&self, source: &Source, params: &OffersParams) -> Result<OffersResponse, Error> {
         let url = format!(
             "{}/api/{}/{}/offers",
             self.base_url, self.api_version, source
         );
         let pairs = params.to_query_pairs();
         let query: Vec<(&str, &str)> = pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
-        self.get(&url, &query).await
+        self.get(source, &url, &query).await
+    }
+
+    /// Fetches every page without the sort options, then sorts and returns
+    /// `pages` pages starting at the requested one.
+    async fn sort_offers_locally(
+        &self,
+        source: &Source,
+        params: &OffersParams,
+        field: SortField,
+        pages: usize,
+    ) -> Result<OffersResponse, Error> {
+        let unsorted = OffersParams {
+            sort: None,
+            order: None,
+            limit: None,
+            ..params.clone()
+        };
+        let mut items = Vec::new();
+        let mut page = 1;
+        let mut fetched = 0;
+        let server_limit = loop {
+            let response = self
+                .fetch_offers(source, &OffersParams { page, ..unsorted.clone() })
+                .await?;
+            fetched += 1;
+            let done = response.meta.next_page <= 0 || response.result.is_empty();
+            items.extend(response.result);
+            if done {
+                break response.meta.limit;
+            }
+            if fetched >= self.local_sort.max_pages {
+                return Err(Error::InvalidParams(vec![ParamError {
+                    field: "sort",
+                    value: field.to_string(),
+                    message: format!(
+                        "not supported by the server for this source, and the results span more than {} pages to sort locally",
+                        self.local_sort.max_pages
+                    ),
+                    suggestion: None,
+                }]));
+            }
+            page = response.meta.next_page;
+        };
+
+        let items = sort::sort_items(items, field, params.order.unwrap_or_default());
+        let page = params.page.max(1);
+        let limit = params.limit.unwrap_or(server_limit).max(1);
+        let start = (page as usize - 1).saturating_mul(limit as usize);
+        let count = (limit as usize).saturating_mul(pages.max(1));
+        let has_more = items.len() > start.saturating_add(count);
+        let result = items.into_iter().skip(start).take(count).collect();
+        let pages = i32::try_from(pages.max(1)).unwrap_or(i32::MAX);
+
+        Ok(OffersResponse {
+            result,
+            meta: Meta {
+                page,
+                next_page: if has_more { page.saturating_add(pages) } else { 0 },
+                limit,
+            },
+            sorted_locally: true,
+        })
+    }
+
+    /// Returns a stream over all offers matching `params`, following
+    /// `next_page` until the last page. See [`OffersStream`] for limits and prefetching.
+    pub fn offers_stream(&self, source: impl Into<Source>, params: &OffersParams) -> OffersStream {
+        OffersStream::new(self.clone(), source.into(), params)
     }
 
     /// Returns a single offer by inner_id.
     pub async fn get_offer(
         &self,
-        source: &str,
+        source: impl Into<Source>,
         inner_id: &str,
     ) -> Result<OffersResponse, Error> {
+        let source = source.into();
         let url = format!(
             "{}/api/{}/{}/offer",
             self.base_url, self.api_version, source
         );
-        self.get(&url, &[("inner_id", inner_id)]).await
+        self.get(&source, &url, &[("inner_id", inner_id)]).await
     }
 
     /// Returns a change_id for the given date (format: yyyy-mm-dd).
-    pub async fn get_change_id(&self, source: &str, date: &str) -> Result<i64, Error> {
+    pub async fn get_change_id(&self, source: impl Into<Source>, date: &str) -> Result<i64, Error> {
+        let source = source.into();
         let url = format!(
             "{}/api/{}/{}/change_id",
             self.base_url, self.api_version, source
         );
-        let result: ChangeIdResponse = self.get(&url, &[("date", date)]).await?;
+        let result: ChangeIdResponse = self.get(&source, &url, &[("date", date)]).await?;
         Ok(result.change_id)
     }
 
     /// Returns a changes feed (added/changed/removed) starting from change_id.
     pub async fn get_changes(
         &self,
-        source: &str,
+        source: impl Into<Source>,
         change_id: i64,
     ) -> Result<ChangesResponse, Error> {
+        let source = source.into();
         let url = format!(
             "{}/api/{}/{}/changes",
             self.base_url, self.api_version, source
         );
         let change_id_str = change_id.to_string();
-        self.get(&url, &[("change_id", &change_id_str)]).await
+        let response: ChangesResponse = self.get(&source, &url, &[("change_id", &change_id_str)]).await?;
+        self.record_items(&source, "changes", response.result.len());
+        Ok(response)
+    }
+
+    /// Returns an endless stream over the changes feed starting at `start`.
+    /// See [`ChangesStream`] for polling and resuming.
+    pub fn changes_stream(
+        &self,
+        source: impl Into<Source>,
+        start: impl Into<ChangesStart>,
+    ) -> ChangesStream {
+        ChangesStream::new(self.clone(), source.into(), start.into())
+    }
+
+    /// Processes the changes feed batch by batch until it is caught up and
+    /// returns the final cursor.
+    ///
+    /// Starts from the cursor saved in `store` for `source`, or from `start`
+    /// when nothing is saved yet. The cursor is saved after `handler` succeeds
+    /// for a batch; a failed batch is not committed and is delivered again
+    /// on the next run.
+    pub async fn sync_changes<S, F, Fut, E>(
+        &self,
+        source: impl Into<Source>,
+        store: &S,
+        start: impl Into<ChangesStart>,
+        mut handler: F,
+    ) -> Result<ChangeCursor, E>
+    where
+        S: CheckpointStore + ?Sized,
+        F: FnMut(Vec<ChangeItem>) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: From<Error>,
+    {
+        let source = source.into();
+        let mut cursor = match store.load(source.as_str())? {
+            Some(cursor) => cursor,
+            None => match start.into() {
+                ChangesStart::ChangeId(change_id) => ChangeCursor::new(change_id),
+                ChangesStart::Date(date) => ChangeCursor::new(self.get_change_id(&source, &date).await?),
+                ChangesStart::Cursor(cursor) => cursor,
+            },
+        };
+
+        loop {
+            let response = self.get_changes(&source, cursor.change_id).await?;
+            let next = response.meta.next_change_id;
+            let received = response.result.len();
+            let items: Vec<ChangeItem> = response.result.into_iter().skip(cursor.offset).collect();
+
+            if items.is_empty() {
+                if next > cursor.change_id {
+                    cursor = ChangeCursor::new(next);
+                    store.save(source.as_str(), cursor)?;
+                    continue;
+                }
+                return Ok(cursor);
+            }
+
+            handler(items).await?;
+
+            cursor = if next > cursor.change_id {
+                ChangeCursor::new(next)
+            } else {
+                ChangeCursor {
+                    change_id: cursor.change_id,
+                    offset: received,
+                }
+            };
+            store.save(source.as_str(), cursor)?;
+        }
     }
 
     /// Returns offer data by its URL on the marketplace.
@@ -108,76 +434,237 @@ impl Client {
         let mut body = HashMap::new();
         body.insert("url", offer_url);
 
-        let mut headers = HeaderMap::new();
-        headers.insert("x-api-key", HeaderValue::from_str(&self.api_key).unwrap());
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        self.execute(None, "offer/info", &[], |key| {
+            Ok(self
+                .http_client
+                .post(&url)
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .header("x-api-key", auth::key_header(key)?)
+                .json(&body))
+        })
+        .await
+    }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+    fn record_items(&self, source: &Source, endpoint: &str, items: usize) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_items(source.as_str(), endpoint, items);
+        }
+    }
 
-        self.handle_response(response).await
+    async fn cached_filters(&self, source: &Source) -> Result<Arc<Filters>, Error> {
+        match self.filters_cache.get(source.as_str(), self.spawner.is_some()) {
+            Cached::Fresh(filters) => return Ok(filters),
+            Cached::Stale { filters, refresh } if self.spawner.is_some() => {
+                if refresh {
+                    self.refresh_filters(source);
+                }
+                return Ok(filters);
+            }
+            _ => {}
+        }
+
+        let filters = Arc::new(Filters::from_value(self.fetch_filters(source).await?));
+        self.filters_cache.insert(source.as_str(), filters.clone());
+        Ok(filters)
+    }
+
+    fn refresh_filters(&self, source: &Source) {
+        let Some(spawner) = &self.spawner else {
+            return;
+        };
+        let client = self.clone();
+        let source = source.clone();
+        (spawner.0)(Box::pin(async move {
+            match client.fetch_filters(&source).await {
+                Ok(raw) => client
+                    .filters_cache
+                    .insert(source.as_str(), Arc::new(Filters::from_value(raw))),
+                Err(_) => client.filters_cache.refresh_failed(source.as_str()),
+            }
+        }));
+    }
+
+    async fn fetch_filters(&self, source: &Source) -> Result<Value, Error> {
+        let url = format!(
+            "{}/api/{}/{}/filters",
+            self.base_url, self.api_version, source
+        );
+        self.get(source, &url, &[]).await
     }
 
     async fn get<T: serde::de::DeserializeOwned>(
         &self,
+        source: &Source,
         url: &str,
         query: &[(&str, &str)],
     ) -> Result<T, Error> {
-        let mut all_query: Vec<(&str, &str)> = query.to_vec();
-        all_query.push(("api_key", &self.api_key));
-
-        let response = self
-            .http_client
-            .get(url)
-            .query(&all_query)
-            .send()
-            .await?;
+        let endpoint = url.rsplit('/').next().unwrap_or_default();
+        self.execute(Some(source.as_str()), endpoint, query, |key| {
+            let mut request = self.http_client.get(url).query(query);
+            if self.auth_mode.uses_query() {
+                request = request.query(&[("api_key", key)]);
+            }
+            if self.auth_mode.uses_header() {
+                request = request.header("x-api-key", auth::key_header(key)?);
+            }
+            Ok(request)
+        })
+        .await
+    }
 
-        self.handle_response(response).await
+    /// Sends the request built by `build` with the current API key, waiting
+    /// for the rate limits of `source` and retrying according to the retry policy.
+    /// `endpoint` and `query` only label the span and metrics.
+    async fn execute<T, F>(
+        &self,
+        source: Option<&str>,
+        endpoint: &str,
+        query: &[(&str, &str)],
+        build: F,
+    ) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+        F: Fn(&str) -> Result<reqwest::RequestBuilder, Error>,
+    {
+        let span = telemetry::request_span(source, endpoint, query);
+        let started = Instant::now();
+        let mut attempts = Attempts::new(&self.retry_policy);
+        let mut received = None;
+
+        let result = async {
+            loop {
+                let key = self.credentials.api_key()?;
+                let request = build(&key)?.build()?;
+                let sent = middleware::run(&self.middleware, request, |request| async {
+                    let _permit = self.limits.acquire(source).await;
+                    self.send(request).await
+                })
+                .await;
+                received = sent.as_ref().ok().map(|r| (r.status, r.body.len() as u64));
+                if let Some(delay) = attempts.retry(&sent) {
+                    telemetry::record_retry(&span, attempts.retries(), delay);
+                    futures_timer::Delay::new(delay).await;
+                    continue;
+                }
+                let response = sent?;
+                let response_span = telemetry::response_span(&span, response.status);
+                let result = telemetry::in_scope(&response_span, || self.handle_response(&span, response));
+                credentials::report_rejected(&*self.credentials, &key, &result);
+                return result;
+            }
+        };
+        let result = telemetry::instrument(result, span.clone()).await;
+
+        let latency = started.elapsed();
+        let retries = attempts.retries();
+        telemetry::record_outcome(&span, latency, retries, &result);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_request(&RequestMetrics {
+                source,
+                endpoint,
+                outcome: Outcome::of(&result),
+                status: received.map(|(status, _)| status),
+                latency,
+                retries,
+                bytes: received.map_or(0, |(_, bytes)| bytes),
+            });
+        }
+        result
+    }
+
+    /// Sends `request` through the tower service if one is configured,
+    /// otherwise through the reqwest client.
+    async fn send(&self, request: reqwest::Request) -> Result<RawResponse, Error> {
+        #[cfg(feature = "tower")]
+        if let Some(service) = &self.service {
+            return service.send(request).await;
+        }
+        Ok(RawResponse::read(self.http_client.execute(request).await?).await?)
     }
 
-    async fn handle_response<T: serde::de::DeserializeOwned>(
+    fn handle_response<T: serde::de::DeserializeOwned>(
         &self,
-        response: reqwest::Response,
+        span: &Span,
+        response: RawResponse,
     ) -> Result<T, Error> {
-        let status = response.status().as_u16();
-        let body = response.text().await?;
+        telemetry::record_response(span, response.status, response.body.len());
+        parse_response(response)
+    }
+}
 
-        if status < 200 || status >= 300 {
-            let mut message = format!("API error: {}", status);
+/// Maps an error status to the matching `Error` variant, or decodes the body.
+pub(crate) fn parse_response<T: serde::de::DeserializeOwned>(response: RawResponse) -> Result<T, Error> {
+    let RawResponse { status, headers, body, .. } = response;
+    let retry_after = retry::retry_after(&headers);
 
-            if let Ok(parsed) = serde_json::from_str::<Value>(&body) {
-                if let Some(msg) = parsed.get("message").and_then(|m| m.as_str()) {
-                    message = msg.to_string();
-                }
-            }
+    if !(200..300).contains(&status) {
+        let mut message = format!("API error: {}", status);
 
-            if status == 401 || status == 403 {
-                return Err(Error::Auth {
-                    status_code: status,
-                    message,
-                });
+        if let Ok(parsed) = serde_json::from_str::<Value>(&body) {
+            if let Some(msg) = parsed.get("message").and_then(|m| m.as_str()) {
+                message = msg.to_string();
             }
+        }
 
-            return Err(Error::Api {
+        return Err(match status {
+            401 | 403 => Error::Auth {
+                status_code: status,
+                message,
+            },
+            404 => Error::NotFound { message },
+            429 => Error::RateLimited {
+                retry_after,
+                message,
+            },
+            400 | 422 => Error::InvalidRequest {
                 status_code: status,
                 message,
                 body,
-            });
-        }
+            },
+            _ => Error::Api {
+                status_code: status,
+                message,
+                body,
+            },
+        });
+    }
 
-        serde_json::from_str(&body).map_err(|_| Error::Api {
-            status_code: status,
-            message: format!(
-                "Invalid JSON response: {}",
-                &body[..body.len().min(200)]
-            ),
-            body,
-        })
+    decode(&body)
+}
+
+/// Deserializes `body`, reporting the JSON path and the text around a failure.
+fn decode<T: serde::de::DeserializeOwned>(body: &str) -> Result<T, Error> {
+    let mut deserializer = serde_json::Deserializer::from_str(body);
+    let result = serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|e| (e.path().to_string(), e.into_inner()))
+        .and_then(|value| match deserializer.end() {
+            Ok(()) => Ok(value),
+            Err(e) => Err((".".to_string(), e)),
+        });
+
+    result.map_err(|(path, e)| Error::Decode {
+        path,
+        snippet: snippet(body, e.line(), e.column()),
+        message: e.to_string(),
+    })
+}
+
+/// Returns up to 40 bytes on either side of a 1-based line and column.
+fn snippet(body: &str, line: usize, column: usize) -> String {
+    let line_start: usize = body
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let offset = (line_start + column.saturating_sub(1)).min(body.len());
+
+    let mut start = offset.saturating_sub(40);
+    while !body.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (offset + 40).min(body.len());
+    while !body.is_char_boundary(end) {
+        end += 1;
     }
+    body[start..end].to_string()
 }
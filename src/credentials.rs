@@ -0,0 +1,195 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::error::Error;
+use crate::secret::ApiKey;
+
+/// Supplies the API key, consulted before every request attempt.
+///
+/// `Debug` implementations must not print the key.
+pub trait CredentialsProvider: fmt::Debug + Send + Sync {
+    /// Returns the key to send with the next request.
+    fn api_key(&self) -> Result<String, Error>;
+
+    /// Called when a request made with `key` failed with `Error::Auth`.
+    fn report_auth_failure(&self, key: &str) {
+        let _ = key;
+    }
+}
+
+/// Tells `credentials` that `key` was rejected if `result` is `Error::Auth`.
+pub(crate) fn report_rejected<T>(
+    credentials: &dyn CredentialsProvider,
+    key: &str,
+    result: &Result<T, Error>,
+) {
+    if let Err(Error::Auth { .. }) = result {
+        credentials.report_auth_failure(key);
+    }
+}
+
+impl<T: CredentialsProvider + ?Sized> CredentialsProvider for Arc<T> {
+    fn api_key(&self) -> Result<String, Error> {
+        (**self).api_key()
+    }
+
+    fn report_auth_failure(&self, key: &str) {
+        (**self).report_auth_failure(key)
+    }
+}
+
+/// A single fixed key. Used by `Client::new` and `Client::builder`.
+#[derive(Debug, Clone)]
+pub struct StaticCredentials {
+    key: ApiKey,
+}
+
+impl StaticCredentials {
+    /// Creates a provider that always returns `key`.
+    pub fn new(key: &str) -> Self {
+        Self { key: ApiKey::new(key) }
+    }
+}
+
+impl CredentialsProvider for StaticCredentials {
+    fn api_key(&self) -> Result<String, Error> {
+        Ok(self.key.expose().to_string())
+    }
+}
+
+/// Reads the key from an environment variable on every request.
+#[derive(Debug, Clone)]
+pub struct EnvCredentials {
+    var: String,
+}
+
+impl EnvCredentials {
+    /// Creates a provider reading the variable `var`, e.g. `"AUTO_API_KEY"`.
+    pub fn new(var: &str) -> Self {
+        Self { var: var.to_string() }
+    }
+}
+
+impl CredentialsProvider for EnvCredentials {
+    fn api_key(&self) -> Result<String, Error> {
+        match std::env::var(&self.var) {
+            Ok(key) if !key.trim().is_empty() => Ok(key.trim().to_string()),
+            Ok(_) => Err(Error::Credentials(format!("environment variable {} is empty", self.var))),
+            Err(e) => Err(Error::Credentials(format!("environment variable {}: {}", self.var, e))),
+        }
+    }
+}
+
+/// Reads the key from a file and reloads it when the file changes.
+///
+/// The file's modification time and size are checked on every request. If
+/// the file is briefly missing while being replaced, the last key is used.
+#[derive(Debug)]
+pub struct FileCredentials {
+    path: PathBuf,
+    cached: Mutex<Option<(SystemTime, u64, ApiKey)>>,
+}
+
+impl FileCredentials {
+    /// Creates a provider reading the key from `path`. Surrounding whitespace is ignored.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    fn error(&self, message: impl fmt::Display) -> Error {
+        Error::Credentials(format!("{}: {}", self.path.display(), message))
+    }
+}
+
+impl CredentialsProvider for FileCredentials {
+    fn api_key(&self) -> Result<String, Error> {
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                return match &*cached {
+                    Some((_, _, key)) => Ok(key.expose().to_string()),
+                    None => Err(self.error(e)),
+                }
+            }
+        };
+        let modified = metadata.modified().map_err(|e| self.error(e))?;
+        if let Some((time, len, key)) = &*cached {
+            if *time == modified && *len == metadata.len() {
+                return Ok(key.expose().to_string());
+            }
+        }
+
+        let contents = fs::read_to_string(&self.path).map_err(|e| self.error(e))?;
+        let key = contents.trim();
+        if key.is_empty() {
+            return Err(self.error("file is empty"));
+        }
+        *cached = Some((modified, metadata.len(), ApiKey::new(key)));
+        Ok(key.to_string())
+    }
+}
+
+/// Several keys used in turn. A key rejected with `Error::Auth` is taken out
+/// of rotation; once all are rejected, requests fail with `Error::Credentials`.
+///
+/// Wrap it in an `Arc` to share the rotation between clients.
+#[derive(Debug)]
+pub struct KeyPool {
+    keys: Vec<(ApiKey, AtomicBool)>,
+    next: AtomicUsize,
+}
+
+impl KeyPool {
+    /// Creates a pool from the given keys.
+    pub fn new<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            keys: keys
+                .into_iter()
+                .map(|key| (ApiKey::new(key.as_ref()), AtomicBool::new(true)))
+                .collect(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of keys still in rotation.
+    pub fn available(&self) -> usize {
+        self.keys
+            .iter()
+            .filter(|(_, active)| active.load(Ordering::Relaxed))
+            .count()
+    }
+}
+
+impl CredentialsProvider for KeyPool {
+    fn api_key(&self) -> Result<String, Error> {
+        let len = self.keys.len();
+        if len == 0 {
+            return Err(Error::Credentials("the key pool is empty".to_string()));
+        }
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(|i| &self.keys[(start + i) % len])
+            .find(|(_, active)| active.load(Ordering::Relaxed))
+            .map(|(key, _)| key.expose().to_string())
+            .ok_or_else(|| Error::Credentials(format!("all {} API keys were rejected", len)))
+    }
+
+    fn report_auth_failure(&self, key: &str) {
+        if let Some((_, active)) = self.keys.iter().find(|(k, _)| k.expose() == key) {
+            active.store(false, Ordering::Relaxed);
+        }
+    }
+}
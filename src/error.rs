@@ -1,21 +1,91 @@
 use std::fmt;
+use std::time::Duration;
+
+use crate::retry::NetworkFailure;
+use crate::secret::redact_url;
 
 /// Error type for all client operations.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Authentication error (401/403).
     Auth {
         status_code: u16,
         message: String,
     },
+    /// Too many requests (429). `retry_after` comes from the `Retry-After` header.
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// The resource doesn't exist (404), e.g. an unknown source or offer.
+    NotFound { message: String },
+    /// The server rejected the request as malformed (400 or 422).
+    InvalidRequest {
+        status_code: u16,
+        message: String,
+        body: String,
+    },
     /// API error (other HTTP error codes).
     Api {
         status_code: u16,
         message: String,
         body: String,
     },
+    /// The request timed out, in reqwest or in a `tower` timeout layer.
+    Timeout(Box<dyn std::error::Error + Send + Sync>),
+    /// The response isn't valid JSON or doesn't match the expected type.
+    Decode {
+        /// Where decoding failed, e.g. `result[3].created_at` (`.` for the root).
+        path: String,
+        message: String,
+        /// The part of the body around the error.
+        snippet: String,
+    },
     /// Network/transport error (reqwest error).
     Network(reqwest::Error),
+    /// Error returned by a custom transport service set with
+    /// `ClientBuilder::tower_service`.
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+    /// No API key is available from the credentials provider.
+    Credentials(String),
+    /// Checkpoint store error (reading or writing a saved cursor).
+    Checkpoint(Box<dyn std::error::Error + Send + Sync>),
+    /// Offer search parameters that don't match the source's filters.
+    InvalidParams(Vec<ParamError>),
+}
+
+/// A single invalid value found by `OffersParams::validate()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamError {
+    pub field: &'static str,
+    pub value: String,
+    pub message: String,
+    /// Closest valid value, if one is similar enough.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}: {}", self.field, self.value, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean {:?}?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error {
+    /// Returns true for errors that may succeed when the request is repeated:
+    /// rate limiting, timeouts, dropped or refused connections and 500/502/503/504.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::RateLimited { .. } | Error::Timeout(_) => true,
+            Error::Api { status_code, .. } => matches!(status_code, 500 | 502 | 503 | 504),
+            Error::Network(e) => NetworkFailure::of(e).is_some(),
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -25,12 +95,42 @@ impl fmt::Display for Error {
                 status_code,
                 message,
             } => write!(f, "auth error {}: {}", status_code, message),
+            Error::RateLimited {
+                retry_after: Some(delay),
+                message,
+            } => write!(f, "rate limited (retry after {}s): {}", delay.as_secs(), message),
+            Error::RateLimited { message, .. } => write!(f, "rate limited: {}", message),
+            Error::NotFound { message } => write!(f, "not found: {}", message),
+            Error::InvalidRequest {
+                status_code,
+                message,
+                ..
+            } => write!(f, "invalid request {}: {}", status_code, message),
             Error::Api {
                 status_code,
                 message,
                 ..
             } => write!(f, "API error {}: {}", status_code, message),
+            Error::Timeout(e) => write!(f, "timeout: {}", e),
+            Error::Decode {
+                path,
+                message,
+                snippet,
+            } => write!(f, "decode error at {}: {} (near {:?})", path, message, snippet),
             Error::Network(e) => write!(f, "network error: {}", e),
+            Error::Transport(e) => write!(f, "transport error: {}", e),
+            Error::Credentials(message) => write!(f, "credentials error: {}", message),
+            Error::Checkpoint(e) => write!(f, "checkpoint error: {}", e),
+            Error::InvalidParams(errors) => {
+                write!(f, "invalid params: ")?;
+                for (i, e) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", e)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -39,13 +139,22 @@ impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Error::Network(e) => Some(e),
+            Error::Timeout(e) | Error::Checkpoint(e) | Error::Transport(e) => Some(e.as_ref()),
             _ => None,
         }
     }
 }
 
 impl From<reqwest::Error> for Error {
-    fn from(e: reqwest::Error) -> Self {
-        Error::Network(e)
+    /// Removes the API key from the URL the error carries.
+    fn from(mut e: reqwest::Error) -> Self {
+        if let Some(url) = e.url_mut() {
+            redact_url(url);
+        }
+        if e.is_timeout() {
+            Error::Timeout(Box::new(e))
+        } else {
+            Error::Network(e)
+        }
     }
 }
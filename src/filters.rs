@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+/// Filter catalogue of a source, returned by `get_filters_typed()`.
+///
+/// Built leniently from the raw response: lists may contain plain strings or
+/// objects with a `name`, and the brand tree may be nested maps or lists.
+/// The raw response stays available in `raw` for source-specific extras.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filters {
+    pub brands: Vec<Brand>,
+    pub body_types: Vec<String>,
+    pub transmissions: Vec<String>,
+    pub engine_types: Vec<String>,
+    pub colors: Vec<String>,
+    pub raw: Value,
+}
+
+/// A brand with its models.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Brand {
+    pub name: String,
+    pub models: Vec<Model>,
+}
+
+/// A model with its configurations.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Model {
+    pub name: String,
+    pub configurations: Vec<String>,
+}
+
+const BRAND_KEYS: [&str; 4] = ["brands", "marks", "brand", "mark"];
+const MODEL_KEYS: [&str; 2] = ["models", "model"];
+const CONFIGURATION_KEYS: [&str; 4] = ["configurations", "configuration", "generations", "generation"];
+const NAME_KEYS: [&str; 6] = ["name", "title", "value", "brand", "mark", "model"];
+
+impl Filters {
+    /// Builds the catalogue from a raw `get_filters` response.
+    pub fn from_value(raw: Value) -> Self {
+        Self {
+            brands: field(&raw, &BRAND_KEYS).map(parse_brands).unwrap_or_default(),
+            body_types: list(&raw, &["body_types", "body_type"]),
+            transmissions: list(&raw, &["transmissions", "transmission", "transmission_type"]),
+            engine_types: list(&raw, &["engine_types", "engine_type"]),
+            colors: list(&raw, &["colors", "color"]),
+            raw,
+        }
+    }
+
+    /// Finds a brand by name, ignoring case.
+    pub fn brand(&self, name: &str) -> Option<&Brand> {
+        self.brands.iter().find(|b| b.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the models of a brand, ignoring case.
+    pub fn models(&self, brand: &str) -> Option<&[Model]> {
+        self.brand(brand).map(|b| b.models.as_slice())
+    }
+
+    /// Returns the names of all brands.
+    pub fn brand_names(&self) -> impl Iterator<Item = &str> {
+        self.brands.iter().map(|b| b.name.as_str())
+    }
+}
+
+impl Brand {
+    /// Finds a model by name, ignoring case.
+    pub fn model(&self, name: &str) -> Option<&Model> {
+        self.models.iter().find(|m| m.name.eq_ignore_ascii_case(name))
+    }
+}
+
+fn field<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a Value> {
+    keys.iter().find_map(|key| value.get(*key))
+}
+
+fn name_of(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Object(_) => field(value, &NAME_KEYS).and_then(|v| match v {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Reads a list of names from the first matching key, either as an array
+/// or as the keys of an object.
+fn list(raw: &Value, keys: &[&str]) -> Vec<String> {
+    match field(raw, keys) {
+        Some(Value::Array(items)) => items.iter().filter_map(name_of).collect(),
+        Some(Value::Object(map)) => map.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Calls `build` for each entry of a tree level: `(name, children)` pairs
+/// from either a map of name to children or an array of names/objects.
+fn entries<T>(value: &Value, child_keys: &[&str], build: impl Fn(String, Option<&Value>) -> T) -> Vec<T> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(name, children)| {
+                let children = field(children, child_keys).or(Some(children));
+                build(name.clone(), children)
+            })
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| {
+                let name = name_of(item)?;
+                Some(build(name, field(item, child_keys)))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_brands(value: &Value) -> Vec<Brand> {
+    entries(value, &MODEL_KEYS, |name, models| Brand {
+        name,
+        models: models.map(parse_models).unwrap_or_default(),
+    })
+}
+
+fn parse_models(value: &Value) -> Vec<Model> {
+    entries(value, &CONFIGURATION_KEYS, |name, configurations| Model {
+        name,
+        configurations: configurations
+            .map(|c| entries(c, &[], |name, _| name))
+            .unwrap_or_default(),
+    })
+}
+
+/// Runs a future in the background; supplied by the caller so the crate
+/// stays independent of any async runtime.
+#[derive(Clone)]
+pub(crate) struct Spawner(pub(crate) Arc<dyn Fn(BoxFuture) + Send + Sync>);
+
+pub(crate) type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+impl fmt::Debug for Spawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Spawner")
+    }
+}
+
+/// Result of a cache lookup.
+pub(crate) enum Cached {
+    Fresh(Arc<Filters>),
+    /// Older than the TTL. `refresh` is true for the one caller that should
+    /// reload it; others keep serving the stale value meanwhile.
+    Stale { filters: Arc<Filters>, refresh: bool },
+    Missing,
+}
+
+struct Entry {
+    filters: Arc<Filters>,
+    fetched_at: Instant,
+    refreshing: bool,
+}
+
+/// Filters per source, shared by all clones of a client.
+/// A zero TTL disables caching.
+pub(crate) struct FiltersCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl FiltersCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Looks up `source`. With `claim_refresh`, a stale entry is marked as
+    /// refreshing so concurrent callers don't start a second reload.
+    pub(crate) fn get(&self, source: &str, claim_refresh: bool) -> Cached {
+        let mut entries = self.entries();
+        let Some(entry) = entries.get_mut(source) else {
+            return Cached::Missing;
+        };
+
+        if entry.fetched_at.elapsed() < self.ttl {
+            return Cached::Fresh(entry.filters.clone());
+        }
+        let refresh = claim_refresh && !entry.refreshing;
+        if refresh {
+            entry.refreshing = true;
+        }
+        Cached::Stale {
+            filters: entry.filters.clone(),
+            refresh,
+        }
+    }
+
+    pub(crate) fn insert(&self, source: &str, filters: Arc<Filters>) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let entry = Entry {
+            filters,
+            fetched_at: Instant::now(),
+            refreshing: false,
+        };
+        self.entries().insert(source.to_string(), entry);
+    }
+
+    /// Releases the refresh claim after a failed background reload.
+    pub(crate) fn refresh_failed(&self, source: &str) {
+        if let Some(entry) = self.entries().get_mut(source) {
+            entry.refreshing = false;
+        }
+    }
+
+    pub(crate) fn remove(&self, source: &str) {
+        self.entries().remove(source);
+    }
+}
@@ -23,10 +23,55 @@
 //! }
 //! ```
 
+mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+mod checkpoint;
 mod client;
+mod credentials;
 mod error;
+mod filters;
+mod metrics;
+mod middleware;
+mod models;
+mod normalize;
+mod params;
+mod rate_limit;
+mod retry;
+mod secret;
+#[cfg(feature = "tower")]
+mod service;
+mod sort;
+mod source;
+mod stream;
+mod telemetry;
 mod types;
+mod validate;
 
+pub use auth::AuthMode;
+pub use builder::ClientBuilder;
+#[cfg(feature = "sqlite")]
+pub use checkpoint::SqliteCheckpointStore;
+pub use checkpoint::{CheckpointStore, JsonFileCheckpointStore, MemoryCheckpointStore};
 pub use client::Client;
-pub use error::Error;
+pub use credentials::{CredentialsProvider, EnvCredentials, FileCredentials, KeyPool, StaticCredentials};
+pub use error::{Error, ParamError};
+pub use filters::{Brand, Filters, Model};
+#[cfg(feature = "metrics")]
+pub use metrics::MetricsCrateRecorder;
+pub use metrics::{MetricsRecorder, Outcome, PrometheusRecorder, RequestMetrics};
+pub use middleware::{Middleware, RawResponse};
+pub use models::*;
+pub use normalize::{FieldError, Money, NormalizedOffer};
+pub use params::OffersParamsBuilder;
+pub use rate_limit::RateLimit;
+pub use retry::RetryPolicy;
+#[cfg(feature = "tower")]
+pub use service::{
+    GetChangeId, GetChanges, GetFilters, GetOffer, GetOfferByUrl, GetOffers, ReqwestService,
+};
+pub use sort::{SortField, SortOrder};
+pub use source::Source;
+pub use stream::{ChangeCursor, ChangesStart, ChangesStream, OffersStream};
 pub use types::*;
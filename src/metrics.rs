@@ -0,0 +1,292 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::error::Error;
+
+/// How a request ended, as counted by [`MetricsRecorder::record_request`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Outcome {
+    /// A 2xx response that decoded successfully.
+    Ok,
+    /// The API key was rejected or couldn't be obtained.
+    Auth,
+    /// Any other error response, or a response that failed to decode.
+    Api,
+    /// Connection failures, timeouts and transport service errors.
+    Network,
+}
+
+impl Outcome {
+    /// Returns the label value: `ok`, `auth`, `api` or `network`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Auth => "auth",
+            Outcome::Api => "api",
+            Outcome::Network => "network",
+        }
+    }
+
+    /// Classifies the result of a request.
+    pub fn of<T>(result: &Result<T, Error>) -> Self {
+        match result {
+            Ok(_) => Outcome::Ok,
+            Err(Error::Auth { .. } | Error::Credentials(_)) => Outcome::Auth,
+            Err(Error::Network(_) | Error::Timeout(_) | Error::Transport(_)) => Outcome::Network,
+            Err(_) => Outcome::Api,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One finished API call, including its retries.
+#[derive(Debug, Clone)]
+pub struct RequestMetrics<'a> {
+    /// Source the request was for; `None` for `get_offer_by_url`.
+    pub source: Option<&'a str>,
+    /// Endpoint name, e.g. `offers`, `changes` or `offer/info`.
+    pub endpoint: &'a str,
+    /// How the call ended.
+    pub outcome: Outcome,
+    /// Status of the final response, if one was received.
+    pub status: Option<u16>,
+    /// Time from the first attempt until the result was decoded.
+    pub latency: Duration,
+    /// Attempts made after the first one.
+    pub retries: u32,
+    /// Size of the final response body.
+    pub bytes: u64,
+}
+
+/// Receives request metrics from [`Client`](crate::Client).
+///
+/// Set with [`ClientBuilder::metrics`](crate::ClientBuilder::metrics).
+/// Methods are called inline on the request path and should not block.
+pub trait MetricsRecorder: fmt::Debug + Send + Sync {
+    /// Called once per API call, after the last retry.
+    fn record_request(&self, request: &RequestMetrics<'_>);
+
+    /// Called with the number of items on each `get_offers` or `get_changes` page.
+    fn record_items(&self, source: &str, endpoint: &str, items: usize) {
+        let _ = (source, endpoint, items);
+    }
+}
+
+impl<T: MetricsRecorder + ?Sized> MetricsRecorder for Arc<T> {
+    fn record_request(&self, request: &RequestMetrics<'_>) {
+        (**self).record_request(request)
+    }
+
+    fn record_items(&self, source: &str, endpoint: &str, items: usize) {
+        (**self).record_items(source, endpoint, items)
+    }
+}
+
+const LATENCY_BUCKETS: [f64; 10] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+const ITEMS_BUCKETS: [f64; 8] = [0.0, 1.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0];
+
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, bounds: &[f64], value: f64) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; bounds.len()];
+        }
+        for (bucket, bound) in self.buckets.iter_mut().zip(bounds) {
+            if value <= *bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str, bounds: &[f64]) {
+        for (bucket, bound) in self.buckets.iter().zip(bounds) {
+            let _ = writeln!(out, "{}_bucket{{{},le=\"{}\"}} {}", name, labels, bound, bucket);
+        }
+        let _ = writeln!(out, "{}_bucket{{{},le=\"+Inf\"}} {}", name, labels, self.count);
+        let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, self.sum);
+        let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, self.count);
+    }
+}
+
+type Key = (String, String);
+
+#[derive(Debug, Default)]
+struct Registry {
+    requests: BTreeMap<(String, String, Outcome), u64>,
+    latency: BTreeMap<Key, Histogram>,
+    bytes: BTreeMap<Key, u64>,
+    items: BTreeMap<Key, Histogram>,
+}
+
+/// Keeps metrics in memory and renders them in the Prometheus text format.
+///
+/// Clones share the same counters, so keep one to serve `/metrics`:
+///
+/// ```no_run
+/// use auto_api_client::{Client, PrometheusRecorder};
+///
+/// # fn main() -> Result<(), auto_api_client::Error> {
+/// let metrics = PrometheusRecorder::new();
+/// let client = Client::builder("your-api-key").metrics(metrics.clone()).build()?;
+/// // later, in the /metrics handler:
+/// let body = metrics.render();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Exported metrics, labelled by `source` and `endpoint`:
+/// `auto_api_requests_total` (also by `outcome`),
+/// `auto_api_request_duration_seconds`, `auto_api_response_bytes_total` and
+/// `auto_api_page_items`.
+#[derive(Debug, Clone, Default)]
+pub struct PrometheusRecorder {
+    registry: Arc<Mutex<Registry>>,
+}
+
+impl PrometheusRecorder {
+    /// Creates an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders all metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        out.push_str("# HELP auto_api_requests_total API calls by outcome.\n");
+        out.push_str("# TYPE auto_api_requests_total counter\n");
+        for ((source, endpoint, outcome), count) in &registry.requests {
+            let _ = writeln!(
+                out,
+                "auto_api_requests_total{{{},outcome=\"{}\"}} {}",
+                labels(source, endpoint),
+                outcome,
+                count
+            );
+        }
+
+        out.push_str("# HELP auto_api_request_duration_seconds API call latency, including retries.\n");
+        out.push_str("# TYPE auto_api_request_duration_seconds histogram\n");
+        for ((source, endpoint), histogram) in &registry.latency {
+            histogram.render(
+                &mut out,
+                "auto_api_request_duration_seconds",
+                &labels(source, endpoint),
+                &LATENCY_BUCKETS,
+            );
+        }
+
+        out.push_str("# HELP auto_api_response_bytes_total Response body bytes received.\n");
+        out.push_str("# TYPE auto_api_response_bytes_total counter\n");
+        for ((source, endpoint), bytes) in &registry.bytes {
+            let _ = writeln!(
+                out,
+                "auto_api_response_bytes_total{{{}}} {}",
+                labels(source, endpoint),
+                bytes
+            );
+        }
+
+        out.push_str("# HELP auto_api_page_items Items returned per offers or changes page.\n");
+        out.push_str("# TYPE auto_api_page_items histogram\n");
+        for ((source, endpoint), histogram) in &registry.items {
+            histogram.render(&mut out, "auto_api_page_items", &labels(source, endpoint), &ITEMS_BUCKETS);
+        }
+
+        out
+    }
+}
+
+impl MetricsRecorder for PrometheusRecorder {
+    fn record_request(&self, request: &RequestMetrics<'_>) {
+        let key = (
+            request.source.unwrap_or_default().to_string(),
+            request.endpoint.to_string(),
+        );
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        *registry
+            .requests
+            .entry((key.0.clone(), key.1.clone(), request.outcome))
+            .or_default() += 1;
+        registry
+            .latency
+            .entry(key.clone())
+            .or_default()
+            .observe(&LATENCY_BUCKETS, request.latency.as_secs_f64());
+        *registry.bytes.entry(key).or_default() += request.bytes;
+    }
+
+    fn record_items(&self, source: &str, endpoint: &str, items: usize) {
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        registry
+            .items
+            .entry((source.to_string(), endpoint.to_string()))
+            .or_default()
+            .observe(&ITEMS_BUCKETS, items as f64);
+    }
+}
+
+fn labels(source: &str, endpoint: &str) -> String {
+    format!("source=\"{}\",endpoint=\"{}\"", escape(source), escape(endpoint))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Forwards metrics to the [`metrics`](https://docs.rs/metrics) crate's
+/// global recorder, under the same names as [`PrometheusRecorder`].
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsCrateRecorder;
+
+#[cfg(feature = "metrics")]
+impl MetricsRecorder for MetricsCrateRecorder {
+    fn record_request(&self, request: &RequestMetrics<'_>) {
+        let source = request.source.unwrap_or_default().to_string();
+        let endpoint = request.endpoint.to_string();
+        metrics::counter!(
+            "auto_api_requests_total",
+            "source" => source.clone(),
+            "endpoint" => endpoint.clone(),
+            "outcome" => request.outcome.as_str()
+        )
+        .increment(1);
+        metrics::histogram!(
+            "auto_api_request_duration_seconds",
+            "source" => source.clone(),
+            "endpoint" => endpoint.clone()
+        )
+        .record(request.latency.as_secs_f64());
+        metrics::counter!("auto_api_response_bytes_total", "source" => source, "endpoint" => endpoint)
+            .increment(request.bytes);
+    }
+
+    fn record_items(&self, source: &str, endpoint: &str, items: usize) {
+        metrics::histogram!(
+            "auto_api_page_items",
+            "source" => source.to_string(),
+            "endpoint" => endpoint.to_string()
+        )
+        .record(items as f64);
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+use reqwest::header::HeaderMap;
+
+use crate::error::Error;
+use crate::secret::redact_url;
+
+/// A response as seen by [`Middleware`], with the body already read.
+#[derive(Clone)]
+pub struct RawResponse {
+    /// URL the response came from. Includes the `api_key` query parameter
+    /// when the key is sent that way.
+    pub url: reqwest::Url,
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers.
+    pub headers: HeaderMap,
+    /// Response body, decoded as UTF-8.
+    pub body: String,
+}
+
+impl RawResponse {
+    /// Creates a response with no headers, e.g. to answer a request from a cache.
+    pub fn new(url: reqwest::Url, status: u16, body: impl Into<String>) -> Self {
+        Self {
+            url,
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    pub(crate) async fn read(response: reqwest::Response) -> Result<Self, reqwest::Error> {
+        let url = response.url().clone();
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+        Ok(Self {
+            url,
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+impl fmt::Debug for RawResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut url = self.url.clone();
+        redact_url(&mut url);
+        f.debug_struct("RawResponse")
+            .field("url", &url.as_str())
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+/// Hooks around every request the client sends, including each retry.
+///
+/// Middleware runs in the order it was added with
+/// [`ClientBuilder::middleware`](crate::ClientBuilder::middleware):
+/// `on_request` from first to last, then `on_response` from last to first.
+/// The request already carries the API key.
+///
+/// ```
+/// use auto_api_client::Middleware;
+/// use reqwest::header::HeaderValue;
+///
+/// #[derive(Debug)]
+/// struct Tenant(&'static str);
+///
+/// impl Middleware for Tenant {
+///     fn on_request(&self, request: &mut reqwest::Request) -> Option<auto_api_client::RawResponse> {
+///         request.headers_mut().insert("x-tenant", HeaderValue::from_static(self.0));
+///         None
+///     }
+/// }
+/// ```
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Inspects or modifies an outgoing request.
+    ///
+    /// Returning a response skips sending the request; the response is passed
+    /// to `on_response` of the middleware before this one only.
+    fn on_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+        let _ = request;
+        None
+    }
+
+    /// Inspects or modifies a response before the client handles it.
+    /// Not called when the request failed without a response.
+    fn on_response(&self, response: &mut RawResponse) {
+        let _ = response;
+    }
+}
+
+impl<T: Middleware + ?Sized> Middleware for Arc<T> {
+    fn on_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+        (**self).on_request(request)
+    }
+
+    fn on_response(&self, response: &mut RawResponse) {
+        (**self).on_response(response)
+    }
+}
+
+/// Passes `request` through `chain`, calling `send` unless a middleware
+/// answered it.
+pub(crate) async fn run<F, Fut>(
+    chain: &[Arc<dyn Middleware>],
+    mut request: reqwest::Request,
+    send: F,
+) -> Result<RawResponse, Error>
+where
+    F: FnOnce(reqwest::Request) -> Fut,
+    Fut: Future<Output = Result<RawResponse, Error>>,
+{
+    let mut answered = None;
+    for (i, middleware) in chain.iter().enumerate() {
+        if let Some(response) = middleware.on_request(&mut request) {
+            answered = Some((i, response));
+            break;
+        }
+    }
+
+    let (entered, mut response) = match answered {
+        Some(answered) => answered,
+        None => (chain.len(), send(request).await?),
+    };
+    for middleware in chain[..entered].iter().rev() {
+        middleware.on_response(&mut response);
+    }
+    Ok(response)
+}
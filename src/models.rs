@@ -0,0 +1,101 @@
+//! Per-source offer models, each behind a cargo feature named after the source.
+//!
+//! The API documents the same fields for every source, so each model is
+//! [`OfferData`]; the per-source names let code state which source it expects.
+//! Anything else the source returns is kept in `OfferData::extra`, so schema
+//! additions never break decoding.
+
+use serde_json::Value;
+
+use crate::source::Source;
+use crate::types::{ChangeItem, OfferItem};
+
+#[cfg(feature = "encar")]
+/// Offer from encar.com (feature `encar`).
+pub type EncarOffer = crate::types::OfferData;
+#[cfg(feature = "mobilede")]
+/// Offer from mobile.de (feature `mobilede`).
+pub type MobileDeOffer = crate::types::OfferData;
+#[cfg(feature = "autoscout24")]
+/// Offer from autoscout24.com (feature `autoscout24`).
+pub type Autoscout24Offer = crate::types::OfferData;
+#[cfg(feature = "che168")]
+/// Offer from che168.com (feature `che168`).
+pub type Che168Offer = crate::types::OfferData;
+#[cfg(feature = "dongchedi")]
+/// Offer from dongchedi.com (feature `dongchedi`).
+pub type DongchediOffer = crate::types::OfferData;
+#[cfg(feature = "guazi")]
+/// Offer from guazi.com (feature `guazi`).
+pub type GuaziOffer = crate::types::OfferData;
+#[cfg(feature = "dubicars")]
+/// Offer from dubicars.com (feature `dubicars`).
+pub type DubicarsOffer = crate::types::OfferData;
+#[cfg(feature = "dubizzle")]
+/// Offer from dubizzle.com (feature `dubizzle`).
+pub type DubizzleOffer = crate::types::OfferData;
+
+/// Offer data decoded into the model of its source.
+///
+/// Sources whose feature is disabled, and unknown sources, decode to `Other`.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TypedOffer {
+    #[cfg(feature = "encar")]
+    Encar(Box<EncarOffer>),
+    #[cfg(feature = "mobilede")]
+    Mobilede(Box<MobileDeOffer>),
+    #[cfg(feature = "autoscout24")]
+    Autoscout24(Box<Autoscout24Offer>),
+    #[cfg(feature = "che168")]
+    Che168(Box<Che168Offer>),
+    #[cfg(feature = "dongchedi")]
+    Dongchedi(Box<DongchediOffer>),
+    #[cfg(feature = "guazi")]
+    Guazi(Box<GuaziOffer>),
+    #[cfg(feature = "dubicars")]
+    Dubicars(Box<DubicarsOffer>),
+    #[cfg(feature = "dubizzle")]
+    Dubizzle(Box<DubizzleOffer>),
+    /// Raw data for sources without a typed model.
+    Other(Value),
+}
+
+impl TypedOffer {
+    /// Decodes `data` into the model for `source`.
+    pub fn from_value(source: &Source, data: Value) -> Result<Self, serde_json::Error> {
+        Ok(match source {
+            #[cfg(feature = "encar")]
+            Source::Encar => TypedOffer::Encar(Box::new(serde_json::from_value(data)?)),
+            #[cfg(feature = "mobilede")]
+            Source::Mobilede => TypedOffer::Mobilede(Box::new(serde_json::from_value(data)?)),
+            #[cfg(feature = "autoscout24")]
+            Source::Autoscout24 => TypedOffer::Autoscout24(Box::new(serde_json::from_value(data)?)),
+            #[cfg(feature = "che168")]
+            Source::Che168 => TypedOffer::Che168(Box::new(serde_json::from_value(data)?)),
+            #[cfg(feature = "dongchedi")]
+            Source::Dongchedi => TypedOffer::Dongchedi(Box::new(serde_json::from_value(data)?)),
+            #[cfg(feature = "guazi")]
+            Source::Guazi => TypedOffer::Guazi(Box::new(serde_json::from_value(data)?)),
+            #[cfg(feature = "dubicars")]
+            Source::Dubicars => TypedOffer::Dubicars(Box::new(serde_json::from_value(data)?)),
+            #[cfg(feature = "dubizzle")]
+            Source::Dubizzle => TypedOffer::Dubizzle(Box::new(serde_json::from_value(data)?)),
+            _ => TypedOffer::Other(data),
+        })
+    }
+}
+
+impl OfferItem {
+    /// Decodes `data` into the typed model for `source`.
+    pub fn decode(&self, source: impl Into<Source>) -> Result<TypedOffer, serde_json::Error> {
+        TypedOffer::from_value(&source.into(), self.data.clone())
+    }
+}
+
+impl ChangeItem {
+    /// Decodes `data` into the typed model for `source`.
+    pub fn decode(&self, source: impl Into<Source>) -> Result<TypedOffer, serde_json::Error> {
+        TypedOffer::from_value(&source.into(), self.data.clone())
+    }
+}
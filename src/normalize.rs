@@ -0,0 +1,306 @@
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::source::Source;
+use crate::types::OfferData;
+
+/// A price with its currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Money {
+    pub amount: f64,
+    /// ISO 4217 code, e.g. "EUR". `None` if it couldn't be determined.
+    pub currency: Option<String>,
+}
+
+/// A field of `OfferData` that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub value: String,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} {:?}: {}", self.field, self.value, self.reason)
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// Typed view of the numeric and date fields of `OfferData`.
+///
+/// Each field is parsed on its own, so one malformed value doesn't hide the others.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedOffer {
+    pub price: Result<Money, FieldError>,
+    pub mileage_km: Result<u32, FieldError>,
+    pub year: Result<u16, FieldError>,
+    /// `Ok(None)` when the source doesn't report displacement (e.g. electric cars).
+    pub displacement_cc: Result<Option<u32>, FieldError>,
+    pub offer_created: Result<SystemTime, FieldError>,
+}
+
+impl NormalizedOffer {
+    /// Returns the errors of all fields that failed to parse.
+    pub fn errors(&self) -> Vec<&FieldError> {
+        [
+            self.price.as_ref().err(),
+            self.mileage_km.as_ref().err(),
+            self.year.as_ref().err(),
+            self.displacement_cc.as_ref().err(),
+            self.offer_created.as_ref().err(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl OfferData {
+    /// Parses price, mileage, year, displacement and creation date.
+    ///
+    /// The price currency defaults to the source's currency unless the price
+    /// string carries its own symbol or code.
+    pub fn normalized(&self, source: impl Into<Source>) -> NormalizedOffer {
+        let source = source.into();
+
+        NormalizedOffer {
+            price: parse_price(&self.price, source.default_currency()),
+            mileage_km: parse_mileage(&self.km_age),
+            year: parse_year(&self.year),
+            displacement_cc: parse_displacement(&self.displacement),
+            offer_created: parse_timestamp(&self.offer_created).ok_or_else(|| FieldError {
+                field: "offer_created",
+                value: self.offer_created.clone(),
+                reason: "expected a date, date-time or unix timestamp",
+            }),
+        }
+    }
+}
+
+const CURRENCY_MARKERS: [(&str, &str); 10] = [
+    ("€", "EUR"),
+    ("EUR", "EUR"),
+    ("$", "USD"),
+    ("USD", "USD"),
+    ("₩", "KRW"),
+    ("KRW", "KRW"),
+    ("¥", "CNY"),
+    ("CNY", "CNY"),
+    ("AED", "AED"),
+    ("CHF", "CHF"),
+];
+
+fn parse_price(value: &str, default_currency: Option<&str>) -> Result<Money, FieldError> {
+    let error = |reason| FieldError {
+        field: "price",
+        value: value.to_string(),
+        reason,
+    };
+
+    let upper = value.to_ascii_uppercase();
+    let currency = CURRENCY_MARKERS
+        .iter()
+        .find(|(marker, _)| upper.contains(marker))
+        .map(|(_, code)| *code)
+        .or(default_currency)
+        .map(str::to_string);
+
+    if value.contains('-') {
+        return Err(error("expected a non-negative amount"));
+    }
+    let amount = parse_decimal(value).ok_or_else(|| error("expected a number"))?;
+
+    Ok(Money { amount, currency })
+}
+
+fn parse_mileage(value: &str) -> Result<u32, FieldError> {
+    parse_integer(value).ok_or_else(|| FieldError {
+        field: "km_age",
+        value: value.to_string(),
+        reason: "expected a whole number of kilometres",
+    })
+}
+
+fn parse_year(value: &str) -> Result<u16, FieldError> {
+    let error = |reason| FieldError {
+        field: "year",
+        value: value.to_string(),
+        reason,
+    };
+
+    let year = parse_integer(value).ok_or_else(|| error("expected a year"))?;
+    if !(1886..=2100).contains(&year) {
+        return Err(error("year out of range"));
+    }
+    Ok(year as u16)
+}
+
+fn parse_displacement(value: &str) -> Result<Option<u32>, FieldError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() || trimmed == "0" {
+        return Ok(None);
+    }
+
+    let parsed = parse_decimal(trimmed).ok_or_else(|| FieldError {
+        field: "displacement",
+        value: value.to_string(),
+        reason: "expected cubic centimetres or litres",
+    })?;
+
+    // Values like "2.0" or "1.6 L" are litres.
+    let cc = if parsed < 20.0 { parsed * 1000.0 } else { parsed };
+    Ok(Some(cc.round() as u32))
+}
+
+/// Parses a number written with either `.` or `,` as the decimal or
+/// thousands separator ("25.000", "25,000.50", "1,6").
+pub(crate) fn parse_decimal(value: &str) -> Option<f64> {
+    let number: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+    let number = number.trim_matches(['.', ',']);
+    if number.is_empty() {
+        return None;
+    }
+
+    let mixed = number.contains('.') && number.contains(',');
+    let normalized: String = match number.rfind(['.', ',']) {
+        // With a single kind of separator, a trailing group of exactly three
+        // digits is a thousands group.
+        Some(i) if !mixed && number.len() - i - 1 == 3 => {
+            number.chars().filter(|c| c.is_ascii_digit()).collect()
+        }
+        Some(i) => number[..i]
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .chain(std::iter::once('.'))
+            .chain(number[i + 1..].chars())
+            .collect(),
+        None => number.to_string(),
+    };
+    normalized.parse().ok()
+}
+
+/// Parses an integer that may contain thousands separators and a unit.
+fn parse_integer(value: &str) -> Option<u32> {
+    let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() || value.contains('-') {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Parses "yyyy-mm-dd", "yyyy-mm-dd hh:mm:ss", RFC 3339 date-times and unix
+/// timestamps in seconds, or in milliseconds when they have 13 digits.
+/// Returns `None` for times `SystemTime` can't represent.
+pub(crate) fn parse_timestamp(value: &str) -> Option<SystemTime> {
+    let value = value.trim();
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        let number: u64 = value.parse().ok()?;
+        let since_epoch = if (1_000_000_000_000..10_000_000_000_000).contains(&number) {
+            Duration::from_millis(number)
+        } else {
+            Duration::from_secs(number)
+        };
+        return UNIX_EPOCH.checked_add(since_epoch);
+    }
+
+    let date = value.get(..value.len().min(10))?;
+    let rest = &value[date.len()..];
+    let mut parts = date.split('-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if parts.next().is_some()
+        || !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+    {
+        return None;
+    }
+
+    let mut seconds = days_from_civil(year, month, day) * 86_400;
+    let mut nanos = 0u32;
+    let rest = rest.trim_start_matches(['T', 't', ' ']);
+
+    if !rest.is_empty() {
+        let (time, offset) = match rest.find(['Z', 'z', '+', '-']) {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+        let (time, fraction) = match time.split_once('.') {
+            Some((time, fraction)) => (time, fraction),
+            None => (time, ""),
+        };
+
+        let mut hms = time.trim().split(':');
+        let hour: i64 = hms.next()?.parse().ok()?;
+        let minute: i64 = hms.next()?.parse().ok()?;
+        let second: i64 = hms.next().unwrap_or("0").parse().ok()?;
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+        seconds += hour * 3600 + minute * 60 + second;
+
+        if !fraction.is_empty() {
+            let digits: String = fraction.chars().take(9).collect();
+            nanos = format!("{:0<9}", digits).parse().ok()?;
+        }
+
+        match offset.chars().next() {
+            None => {}
+            Some('Z' | 'z') if offset.len() == 1 => {}
+            Some(sign @ ('+' | '-')) => {
+                let shift = parse_offset(&offset[1..])?;
+                seconds += if sign == '+' { -shift } else { shift };
+            }
+            Some(_) => return None,
+        }
+    }
+
+    if seconds < 0 {
+        return None;
+    }
+    UNIX_EPOCH.checked_add(Duration::new(seconds as u64, nanos))
+}
+
+/// Parses a UTC offset without its sign ("hh", "hhmm" or "hh:mm") into seconds.
+fn parse_offset(offset: &str) -> Option<i64> {
+    let (hours, minutes) = match offset.len() {
+        2 => (offset, "00"),
+        4 => offset.split_at(2),
+        5 => offset.split_once(':')?,
+        _ => return None,
+    };
+    if hours.len() != 2 || !hours.bytes().chain(minutes.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(hours * 3600 + minutes * 60)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -0,0 +1,232 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::error::{Error, ParamError};
+use crate::sort::{SortField, SortOrder};
+use crate::types::OffersParams;
+
+impl OffersParams {
+    /// Returns a builder with chained setters and range helpers.
+    ///
+    /// ```
+    /// use auto_api_client::OffersParams;
+    ///
+    /// let params = OffersParams::builder()
+    ///     .page(1)
+    ///     .brand("BMW")
+    ///     .year(2018..=2022)
+    ///     .price(..50000)
+    ///     .extra("fuel", "diesel")
+    ///     .build();
+    ///
+    /// assert_eq!(params.year_from, Some(2018));
+    /// assert_eq!(params.price_to, Some(49999));
+    /// ```
+    pub fn builder() -> OffersParamsBuilder {
+        OffersParamsBuilder::default()
+    }
+
+    /// Encodes [`to_query_pairs`](Self::to_query_pairs) as a URL query string.
+    pub fn to_query_string(&self) -> String {
+        // Encoding string pairs can't fail.
+        serde_urlencoded::to_string(self.to_query_pairs()).unwrap_or_default()
+    }
+
+    /// Parses a query string produced by [`to_query_string`](Self::to_query_string)
+    /// or copied from a URL. A leading `?` is allowed.
+    ///
+    /// Unknown keys go into `extra`; `api_key` is dropped. Returns
+    /// `Error::InvalidParams` listing every value that doesn't parse.
+    ///
+    /// ```
+    /// use auto_api_client::OffersParams;
+    ///
+    /// let params = OffersParams::from_query_string("?page=2&brand=BMW&fuel=diesel").unwrap();
+    /// assert_eq!(params.brand.as_deref(), Some("BMW"));
+    /// assert_eq!(params.to_query_string(), "page=2&brand=BMW&fuel=diesel");
+    /// ```
+    pub fn from_query_string(query: &str) -> Result<Self, Error> {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        let pairs: Vec<(String, String)> = serde_urlencoded::from_str(query).map_err(|e| {
+            Error::InvalidParams(vec![ParamError {
+                field: "query",
+                value: query.to_string(),
+                message: e.to_string(),
+                suggestion: None,
+            }])
+        })?;
+
+        let mut params = OffersParams::default();
+        let mut errors = Vec::new();
+        for (key, value) in pairs {
+            match key.as_str() {
+                "page" => params.page = integer(&mut errors, "page", &value).unwrap_or_default(),
+                "brand" => params.brand = Some(value),
+                "model" => params.model = Some(value),
+                "configuration" => params.configuration = Some(value),
+                "complectation" => params.complectation = Some(value),
+                "transmission" => params.transmission = Some(value),
+                "color" => params.color = Some(value),
+                "body_type" => params.body_type = Some(value),
+                "engine_type" => params.engine_type = Some(value),
+                "year_from" => params.year_from = integer(&mut errors, "year_from", &value),
+                "year_to" => params.year_to = integer(&mut errors, "year_to", &value),
+                "mileage_from" => params.mileage_from = integer(&mut errors, "mileage_from", &value),
+                "mileage_to" => params.mileage_to = integer(&mut errors, "mileage_to", &value),
+                "price_from" => params.price_from = integer(&mut errors, "price_from", &value),
+                "price_to" => params.price_to = integer(&mut errors, "price_to", &value),
+                "limit" => params.limit = integer(&mut errors, "limit", &value),
+                "sort" => {
+                    params.sort = SortField::from_query_value(&value);
+                    if params.sort.is_none() {
+                        errors.push(invalid("sort", value, "expected price, year, mileage or created"));
+                    }
+                }
+                "order" => {
+                    params.order = SortOrder::from_query_value(&value);
+                    if params.order.is_none() {
+                        errors.push(invalid("order", value, "expected asc or desc"));
+                    }
+                }
+                "api_key" => {}
+                _ => {
+                    params.extra.insert(key, value);
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(params)
+        } else {
+            Err(Error::InvalidParams(errors))
+        }
+    }
+}
+
+fn invalid(field: &'static str, value: String, message: &str) -> ParamError {
+    ParamError {
+        field,
+        value,
+        message: message.to_string(),
+        suggestion: None,
+    }
+}
+
+fn integer(errors: &mut Vec<ParamError>, field: &'static str, value: &str) -> Option<i32> {
+    let parsed = value.trim().parse().ok();
+    if parsed.is_none() {
+        errors.push(invalid(field, value.to_string(), "expected an integer"));
+    }
+    parsed
+}
+
+/// Builder for [`OffersParams`], returned by [`OffersParams::builder`].
+///
+/// Range helpers take any Rust range; bounds are inclusive on the API side,
+/// so an exclusive end such as `..50000` becomes `price_to = 49999`.
+#[derive(Debug, Default, Clone)]
+pub struct OffersParamsBuilder {
+    params: OffersParams,
+}
+
+macro_rules! string_setters {
+    ($($(#[$meta:meta])* $name:ident),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            pub fn $name(mut self, value: impl Into<String>) -> Self {
+                self.params.$name = Some(value.into());
+                self
+            }
+        )*
+    };
+}
+
+impl OffersParamsBuilder {
+    /// Sets the page number.
+    pub fn page(mut self, page: i32) -> Self {
+        self.params.page = page;
+        self
+    }
+
+    string_setters!(
+        /// Sets the brand.
+        brand,
+        /// Sets the model.
+        model,
+        /// Sets the configuration.
+        configuration,
+        /// Sets the complectation.
+        complectation,
+        /// Sets the transmission.
+        transmission,
+        /// Sets the color.
+        color,
+        /// Sets the body type.
+        body_type,
+        /// Sets the engine type.
+        engine_type,
+    );
+
+    /// Sets `year_from` and `year_to` from a range, e.g. `2018..=2022` or `2020..`.
+    pub fn year(mut self, range: impl RangeBounds<i32>) -> Self {
+        (self.params.year_from, self.params.year_to) = bounds(range);
+        self
+    }
+
+    /// Sets `mileage_from` and `mileage_to` from a range, e.g. `..100000`.
+    pub fn mileage(mut self, range: impl RangeBounds<i32>) -> Self {
+        (self.params.mileage_from, self.params.mileage_to) = bounds(range);
+        self
+    }
+
+    /// Sets `price_from` and `price_to` from a range, e.g. `10000..=50000`.
+    pub fn price(mut self, range: impl RangeBounds<i32>) -> Self {
+        (self.params.price_from, self.params.price_to) = bounds(range);
+        self
+    }
+
+    /// Sorts by `field` in the given direction.
+    pub fn sort(mut self, field: SortField, order: SortOrder) -> Self {
+        self.params.sort = Some(field);
+        self.params.order = Some(order);
+        self
+    }
+
+    /// Sets the page size.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.params.limit = Some(limit);
+        self
+    }
+
+    /// Adds a source-specific query parameter. A key matching a built-in
+    /// parameter replaces it.
+    pub fn extra(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.params.extra.insert(key.into(), value.to_string());
+        self
+    }
+
+    /// Returns the parameters.
+    pub fn build(self) -> OffersParams {
+        self.params
+    }
+}
+
+impl From<OffersParamsBuilder> for OffersParams {
+    fn from(builder: OffersParamsBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Converts a range into inclusive `(from, to)` bounds.
+fn bounds(range: impl RangeBounds<i32>) -> (Option<i32>, Option<i32>) {
+    let from = match range.start_bound() {
+        Bound::Included(v) => Some(*v),
+        Bound::Excluded(v) => Some(v.saturating_add(1)),
+        Bound::Unbounded => None,
+    };
+    let to = match range.end_bound() {
+        Bound::Included(v) => Some(*v),
+        Bound::Excluded(v) => Some(v.saturating_sub(1)),
+        Bound::Unbounded => None,
+    };
+    (from, to)
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_lock::{Semaphore, SemaphoreGuardArc};
+
+/// Client-side request limits.
+///
+/// Requests that would exceed a limit wait instead of failing.
+/// Limits are shared by all clones of a [`Client`](crate::Client).
+///
+/// ```
+/// use auto_api_client::RateLimit;
+///
+/// let limit = RateLimit {
+///     requests_per_second: Some(5.0),
+///     max_in_flight: Some(4),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RateLimit {
+    /// Sustained request rate of the token bucket. `None` disables it.
+    pub requests_per_second: Option<f64>,
+    /// Number of requests that may be sent at once before the rate applies
+    /// (default: 1 when set to 0).
+    pub burst: u32,
+    /// Maximum number of requests in flight at the same time. `None` means no cap.
+    pub max_in_flight: Option<usize>,
+}
+
+/// Limiters for the default and per-source limits.
+#[derive(Default)]
+pub(crate) struct Limits {
+    default: Option<Limiter>,
+    per_source: HashMap<String, Limiter>,
+}
+
+impl Limits {
+    pub(crate) fn new(default: Option<&RateLimit>, per_source: &HashMap<String, RateLimit>) -> Self {
+        Self {
+            default: default.map(Limiter::new),
+            per_source: per_source
+                .iter()
+                .map(|(source, limit)| (source.clone(), Limiter::new(limit)))
+                .collect(),
+        }
+    }
+
+    /// Waits until a request to `source` is allowed.
+    /// Sources without their own limit share the default one.
+    /// The returned permit must be held until the response has been read.
+    pub(crate) async fn acquire(&self, source: Option<&str>) -> Option<SemaphoreGuardArc> {
+        let limiter = source
+            .and_then(|s| self.per_source.get(s))
+            .or(self.default.as_ref())?;
+        limiter.acquire().await
+    }
+}
+
+struct Limiter {
+    bucket: Option<Mutex<TokenBucket>>,
+    semaphore: Option<Arc<Semaphore>>,
+}
+
+impl Limiter {
+    fn new(limit: &RateLimit) -> Self {
+        Self {
+            bucket: limit
+                .requests_per_second
+                .filter(|rate| *rate > 0.0)
+                .map(|rate| Mutex::new(TokenBucket::new(rate, limit.burst.max(1)))),
+            semaphore: limit
+                .max_in_flight
+                .map(|max| Arc::new(Semaphore::new(max.max(1)))),
+        }
+    }
+
+    async fn acquire(&self) -> Option<SemaphoreGuardArc> {
+        if let Some(bucket) = &self.bucket {
+            loop {
+                let wait = match bucket.lock() {
+                    Ok(mut bucket) => bucket.try_take(),
+                    Err(poisoned) => poisoned.into_inner().try_take(),
+                };
+                match wait {
+                    Some(delay) => futures_timer::Delay::new(delay).await,
+                    None => break,
+                }
+            }
+        }
+
+        match &self.semaphore {
+            Some(semaphore) => Some(semaphore.acquire_arc().await),
+            None => None,
+        }
+    }
+}
+
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, burst: u32) -> Self {
+        Self {
+            rate,
+            capacity: burst as f64,
+            tokens: burst as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Takes a token, or returns how long to wait until one is available.
+    fn try_take(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+use crate::error::Error;
+use crate::middleware::RawResponse;
+
+/// Retry policy applied to every request made by [`Client`](crate::Client).
+///
+/// The default policy makes a single attempt. Enable retries with
+/// `..Default::default()`:
+///
+/// ```
+/// use auto_api_client::RetryPolicy;
+///
+/// let policy = RetryPolicy {
+///     max_attempts: 4,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one (default: 1).
+    pub max_attempts: u32,
+    /// Delay before the first retry (default: 200 ms).
+    pub initial_backoff: Duration,
+    /// Upper bound for the computed backoff (default: 10 seconds).
+    pub max_backoff: Duration,
+    /// Factor the backoff grows by after each attempt (default: 2.0).
+    pub multiplier: f64,
+    /// Randomizes each delay between half and the full backoff (default: true).
+    pub jitter: bool,
+    /// HTTP status codes that trigger a retry (default: 429, 500, 502, 503, 504).
+    pub retry_statuses: Vec<u16>,
+    /// Retries requests that fail with `Error::Timeout` (default: true).
+    pub retry_on_timeout: bool,
+    /// Retries `Error::Network` failures to connect to the server (default: true).
+    pub retry_on_connect: bool,
+    /// Retries `Error::Network` failures while sending the request, e.g. a
+    /// connection closed before the response arrived (default: true).
+    pub retry_on_request: bool,
+    /// Retries `Error::Network` failures while reading the response body,
+    /// e.g. a connection dropped mid-body (default: true).
+    pub retry_on_body: bool,
+    /// Waits for the `Retry-After` header (in seconds) when the server sends
+    /// one, instead of the computed backoff (default: true). A wait longer
+    /// than `max_retry_after` isn't retried: the response is returned at once,
+    /// e.g. as `Error::RateLimited` carrying the requested delay.
+    pub respect_retry_after: bool,
+    /// Longest `Retry-After` the client waits for inside a call (default: 60 seconds).
+    pub max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+            retry_statuses: vec![429, 500, 502, 503, 504],
+            retry_on_timeout: true,
+            retry_on_connect: true,
+            retry_on_request: true,
+            retry_on_body: true,
+            respect_retry_after: true,
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a policy that never retries.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn retries_status(&self, status: u16) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    pub(crate) fn retries_error(&self, error: &Error) -> bool {
+        match error {
+            Error::Timeout(_) => self.retry_on_timeout,
+            Error::Network(e) => match NetworkFailure::of(e) {
+                Some(NetworkFailure::Connect) => self.retry_on_connect,
+                Some(NetworkFailure::Request) => self.retry_on_request,
+                Some(NetworkFailure::Body) => self.retry_on_body,
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the delay before the attempt following `attempt` (1-based),
+    /// or `None` if the server asks to wait longer than `max_retry_after`.
+    pub(crate) fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if self.respect_retry_after {
+            if let Some(delay) = retry_after {
+                return (delay <= self.max_retry_after).then_some(delay);
+            }
+        }
+
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let secs = self.initial_backoff.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        let backoff = if secs.is_finite() && secs < self.max_backoff.as_secs_f64() {
+            Duration::from_secs_f64(secs)
+        } else {
+            self.max_backoff
+        };
+
+        Some(if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            backoff
+        })
+    }
+}
+
+/// Attempts of a single request under a policy. The async and blocking
+/// clients send and sleep their own way and ask it whether to go again.
+pub(crate) struct Attempts<'a> {
+    policy: &'a RetryPolicy,
+    attempt: u32,
+}
+
+impl<'a> Attempts<'a> {
+    pub(crate) fn new(policy: &'a RetryPolicy) -> Self {
+        Self { policy, attempt: 1 }
+    }
+
+    /// Number of retries made so far.
+    pub(crate) fn retries(&self) -> u32 {
+        self.attempt - 1
+    }
+
+    /// Returns the delay before the next attempt if the outcome of the
+    /// current one should be retried, and counts that attempt.
+    pub(crate) fn retry(&mut self, sent: &Result<RawResponse, Error>) -> Option<Duration> {
+        if self.attempt >= self.policy.max_attempts {
+            return None;
+        }
+        let delay = match sent {
+            Ok(response) if self.policy.retries_status(response.status) => {
+                self.policy.delay_for(self.attempt, retry_after(&response.headers))?
+            }
+            Err(e) if self.policy.retries_error(e) => self.policy.delay_for(self.attempt, None)?,
+            _ => return None,
+        };
+        self.attempt += 1;
+        Some(delay)
+    }
+}
+
+/// Stage at which a network error interrupted a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NetworkFailure {
+    Connect,
+    Request,
+    Body,
+}
+
+impl NetworkFailure {
+    /// Classifies `error`, or returns `None` for errors repeating won't fix
+    /// (invalid URL, redirect loop, builder errors).
+    pub(crate) fn of(error: &reqwest::Error) -> Option<Self> {
+        // Connect errors are also request errors, so check them first.
+        // Reading the body with `bytes()`/`text()` reports a decode error.
+        if error.is_connect() {
+            Some(Self::Connect)
+        } else if error.is_body() || error.is_decode() {
+            Some(Self::Body)
+        } else if error.is_request() {
+            Some(Self::Request)
+        } else {
+            None
+        }
+    }
+}
+
+/// Reads a `Retry-After` header given in seconds.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Returns a pseudo-random value in `[0, 1)` without pulling in a RNG crate.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
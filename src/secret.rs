@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// The API key, kept out of `Debug` output.
+#[derive(Clone)]
+pub(crate) struct ApiKey(String);
+
+impl ApiKey {
+    pub(crate) fn new(key: &str) -> Self {
+        Self(key.to_string())
+    }
+
+    /// Returns the key for sending it with a request.
+    pub(crate) fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"[redacted]\"")
+    }
+}
+
+/// Removes the `api_key` query parameter from a URL.
+pub(crate) fn redact_url(url: &mut reqwest::Url) {
+    if !url.query_pairs().any(|(k, _)| k == "api_key") {
+        return;
+    }
+
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| k != "api_key")
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+}
@@ -0,0 +1,355 @@
+//! `tower` integration: a pluggable HTTP transport and the API endpoints as services.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use serde_json::Value;
+use tower::timeout::error::Elapsed;
+use tower::util::BoxCloneSyncService;
+use tower::{Service, ServiceExt};
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::middleware::RawResponse;
+use crate::secret::redact_url;
+use crate::source::Source;
+use crate::types::{ChangesResponse, OffersParams, OffersResponse};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+type BoxFuture<T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send>>;
+
+/// The transport set with `ClientBuilder::tower_service`.
+#[derive(Clone)]
+pub(crate) struct HttpService(BoxCloneSyncService<http::Request<Vec<u8>>, http::Response<Vec<u8>>, BoxError>);
+
+impl fmt::Debug for HttpService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HttpService")
+    }
+}
+
+impl HttpService {
+    pub(crate) fn new<S>(service: S) -> Self
+    where
+        S: Service<http::Request<Vec<u8>>, Response = http::Response<Vec<u8>>> + Clone + Send + Sync + 'static,
+        S::Error: Into<BoxError>,
+        S::Future: Send + 'static,
+    {
+        Self(BoxCloneSyncService::new(service.map_err(Into::into)))
+    }
+
+    pub(crate) async fn send(&self, request: reqwest::Request) -> Result<RawResponse, Error> {
+        let url = request.url().clone();
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(<[u8]>::to_vec)
+            .unwrap_or_default();
+        let mut http_request = http::Request::builder()
+            .method(request.method().clone())
+            .uri(request.url().as_str())
+            .version(request.version())
+            .body(body)
+            .map_err(|e| Error::Transport(e.into()))?;
+        *http_request.headers_mut() = request.headers().clone();
+
+        let response = self
+            .0
+            .clone()
+            .oneshot(http_request)
+            .await
+            .map_err(transport_error)?;
+        let (parts, body) = response.into_parts();
+        Ok(RawResponse {
+            url,
+            status: parts.status.as_u16(),
+            headers: parts.headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
+
+/// Maps an error of the transport. Timeouts anywhere in the `source()` chain,
+/// e.g. behind `tower::buffer`, become `Error::Timeout`.
+fn transport_error(error: BoxError) -> Error {
+    let error = match error.downcast::<reqwest::Error>() {
+        Ok(e) => return Error::from(*e),
+        Err(e) => e,
+    };
+    let mut timed_out = false;
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error.as_ref());
+    while let Some(e) = source {
+        timed_out |= e.is::<Elapsed>()
+            || e.downcast_ref::<reqwest::Error>().is_some_and(reqwest::Error::is_timeout);
+        source = e.source();
+    }
+    if timed_out {
+        Error::Timeout(error)
+    } else {
+        Error::Transport(error)
+    }
+}
+
+/// A `tower::Service` that sends `http` requests with a `reqwest::Client`.
+///
+/// Use it as the innermost service when backing a [`Client`] with your own layers:
+///
+/// ```no_run
+/// use auto_api_client::{Client, ReqwestService};
+///
+/// # fn main() -> Result<(), auto_api_client::Error> {
+/// let transport = tower::ServiceBuilder::new()
+///     // .layer(...) your timeouts, load shedding, buffering
+///     .service(ReqwestService::default());
+/// let client = Client::builder("your-api-key").tower_service(transport).build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestService {
+    client: reqwest::Client,
+}
+
+impl ReqwestService {
+    /// Creates a service sending requests with `client`.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<http::Request<Vec<u8>>> for ReqwestService {
+    type Response = http::Response<Vec<u8>>;
+    type Error = reqwest::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Vec<u8>>) -> Self::Future {
+        let client = self.client.clone();
+        // Layers above may wrap the error where `Client` can't reach it to
+        // remove the API key, so it's removed here.
+        Box::pin(async move {
+            let response = client
+                .execute(reqwest::Request::try_from(request).map_err(redacted)?)
+                .await
+                .map_err(redacted)?;
+            let status = response.status();
+            let version = response.version();
+            let headers = response.headers().clone();
+            let body = response.bytes().await.map_err(redacted)?.to_vec();
+
+            let mut http_response = http::Response::new(body);
+            *http_response.status_mut() = status;
+            *http_response.version_mut() = version;
+            *http_response.headers_mut() = headers;
+            Ok(http_response)
+        })
+    }
+}
+
+fn redacted(mut error: reqwest::Error) -> reqwest::Error {
+    if let Some(url) = error.url_mut() {
+        redact_url(url);
+    }
+    error
+}
+
+/// Request for [`Client::get_filters`] as a `tower::Service`.
+#[derive(Debug, Clone)]
+pub struct GetFilters {
+    /// Source to list the filters of.
+    pub source: Source,
+}
+
+impl GetFilters {
+    /// Creates a request for the filters of `source`.
+    pub fn new(source: impl Into<Source>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+}
+
+/// Request for [`Client::get_offers`] as a `tower::Service`.
+#[derive(Debug, Clone)]
+pub struct GetOffers {
+    /// Source to search.
+    pub source: Source,
+    /// Search parameters.
+    pub params: OffersParams,
+}
+
+impl GetOffers {
+    /// Creates a request for offers of `source` matching `params`.
+    pub fn new(source: impl Into<Source>, params: OffersParams) -> Self {
+        Self {
+            source: source.into(),
+            params,
+        }
+    }
+}
+
+/// Request for [`Client::get_offer`] as a `tower::Service`.
+#[derive(Debug, Clone)]
+pub struct GetOffer {
+    /// Source of the offer.
+    pub source: Source,
+    /// `inner_id` of the offer.
+    pub inner_id: String,
+}
+
+impl GetOffer {
+    /// Creates a request for the offer of `source` with `inner_id`.
+    pub fn new(source: impl Into<Source>, inner_id: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            inner_id: inner_id.into(),
+        }
+    }
+}
+
+/// Request for [`Client::get_change_id`] as a `tower::Service`.
+#[derive(Debug, Clone)]
+pub struct GetChangeId {
+    /// Source of the changes feed.
+    pub source: Source,
+    /// Date to look up (format: yyyy-mm-dd).
+    pub date: String,
+}
+
+impl GetChangeId {
+    /// Creates a request for the first change_id of `source` on `date`.
+    pub fn new(source: impl Into<Source>, date: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            date: date.into(),
+        }
+    }
+}
+
+/// Request for [`Client::get_changes`] as a `tower::Service`.
+#[derive(Debug, Clone)]
+pub struct GetChanges {
+    /// Source of the changes feed.
+    pub source: Source,
+    /// First change to return.
+    pub change_id: i64,
+}
+
+impl GetChanges {
+    /// Creates a request for changes of `source` starting at `change_id`.
+    pub fn new(source: impl Into<Source>, change_id: i64) -> Self {
+        Self {
+            source: source.into(),
+            change_id,
+        }
+    }
+}
+
+/// Request for [`Client::get_offer_by_url`] as a `tower::Service`.
+#[derive(Debug, Clone)]
+pub struct GetOfferByUrl {
+    /// URL of the offer on the marketplace.
+    pub url: String,
+}
+
+impl GetOfferByUrl {
+    /// Creates a request for the offer at `url` on the marketplace.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+impl Service<GetFilters> for Client {
+    type Response = Value;
+    type Error = Error;
+    type Future = BoxFuture<Value>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: GetFilters) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { client.get_filters(request.source).await })
+    }
+}
+
+impl Service<GetOffers> for Client {
+    type Response = OffersResponse;
+    type Error = Error;
+    type Future = BoxFuture<OffersResponse>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: GetOffers) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { client.get_offers(request.source, &request.params).await })
+    }
+}
+
+impl Service<GetOffer> for Client {
+    type Response = OffersResponse;
+    type Error = Error;
+    type Future = BoxFuture<OffersResponse>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: GetOffer) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { client.get_offer(request.source, &request.inner_id).await })
+    }
+}
+
+impl Service<GetChangeId> for Client {
+    type Response = i64;
+    type Error = Error;
+    type Future = BoxFuture<i64>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: GetChangeId) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { client.get_change_id(request.source, &request.date).await })
+    }
+}
+
+impl Service<GetChanges> for Client {
+    type Response = ChangesResponse;
+    type Error = Error;
+    type Future = BoxFuture<ChangesResponse>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: GetChanges) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { client.get_changes(request.source, request.change_id).await })
+    }
+}
+
+impl Service<GetOfferByUrl> for Client {
+    type Response = Value;
+    type Error = Error;
+    type Future = BoxFuture<Value>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: GetOfferByUrl) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { client.get_offer_by_url(&request.url).await })
+    }
+}
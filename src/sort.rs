@@ -0,0 +1,174 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::normalize::parse_decimal;
+use crate::source::Source;
+use crate::types::OfferItem;
+
+/// Field to sort offer searches by.
+///
+/// Sent to the server as `sort`. When the server ignores the ordering for a
+/// source, the client sorts locally instead; see [`OffersResponse::sorted_locally`](crate::OffersResponse::sorted_locally).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortField {
+    Price,
+    Year,
+    Mileage,
+    /// When the offer was added to the feed (`created_at`).
+    Created,
+}
+
+impl SortField {
+    /// Returns the value sent as the `sort` query parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortField::Price => "price",
+            SortField::Year => "year",
+            SortField::Mileage => "mileage",
+            SortField::Created => "created",
+        }
+    }
+
+    /// Parses the value of the `sort` query parameter.
+    pub fn from_query_value(value: &str) -> Option<Self> {
+        [SortField::Price, SortField::Year, SortField::Mileage, SortField::Created]
+            .into_iter()
+            .find(|field| field.as_str().eq_ignore_ascii_case(value))
+    }
+
+    /// Reads the sort key of an offer. `None` if the value is missing or unparseable.
+    fn key(&self, item: &OfferItem) -> Option<f64> {
+        let number = |key: &str| match item.data.get(key)? {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => parse_decimal(s),
+            _ => None,
+        };
+
+        match self {
+            SortField::Price => number("price"),
+            SortField::Year => number("year"),
+            SortField::Mileage => number("km_age"),
+            SortField::Created => item
+                .created_time()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs_f64()),
+        }
+    }
+}
+
+impl fmt::Display for SortField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Sort direction, sent as `order`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    /// Returns the value sent as the `order` query parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+
+    /// Parses the value of the `order` query parameter.
+    pub fn from_query_value(value: &str) -> Option<Self> {
+        [SortOrder::Asc, SortOrder::Desc]
+            .into_iter()
+            .find(|order| order.as_str().eq_ignore_ascii_case(value))
+    }
+
+    fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Checks whether the items that have a sort key are in order; items without
+/// one are skipped. `None` if fewer than two items have a key to compare.
+pub(crate) fn is_ordered<'a>(
+    items: impl IntoIterator<Item = &'a OfferItem>,
+    field: SortField,
+    order: SortOrder,
+) -> Option<bool> {
+    let keys: Vec<f64> = items.into_iter().filter_map(|item| field.key(item)).collect();
+    if keys.len() < 2 {
+        return None;
+    }
+    Some(
+        keys.windows(2)
+            .all(|pair| order.apply(pair[0].total_cmp(&pair[1])) != Ordering::Greater),
+    )
+}
+
+/// Sorts `items` by `field`. Items without a key go last in either order.
+pub(crate) fn sort_items(items: Vec<OfferItem>, field: SortField, order: SortOrder) -> Vec<OfferItem> {
+    let mut keyed: Vec<(Option<f64>, OfferItem)> =
+        items.into_iter().map(|item| (field.key(&item), item)).collect();
+    keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => order.apply(a.total_cmp(b)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    keyed.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Who sorts an ordering of a source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SortedBy {
+    Server,
+    Client,
+}
+
+/// Which orderings the server supports, per source, as far as known.
+/// Shared by all clones of a client.
+pub(crate) struct LocalSort {
+    pub(crate) max_pages: usize,
+    orderings: Mutex<HashMap<(String, SortField), SortedBy>>,
+}
+
+impl LocalSort {
+    /// `local` are the orderings configured to always sort locally.
+    pub(crate) fn new(max_pages: usize, local: HashSet<(String, SortField)>) -> Self {
+        Self {
+            max_pages,
+            orderings: Mutex::new(local.into_iter().map(|key| (key, SortedBy::Client)).collect()),
+        }
+    }
+
+    fn orderings(&self) -> MutexGuard<'_, HashMap<(String, SortField), SortedBy>> {
+        self.orderings.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn get(&self, source: &Source, field: SortField) -> Option<SortedBy> {
+        self.orderings().get(&(source.as_str().to_string(), field)).copied()
+    }
+
+    pub(crate) fn insert(&self, source: &Source, field: SortField, sorted_by: SortedBy) {
+        self.orderings().insert((source.as_str().to_string(), field), sorted_by);
+    }
+}
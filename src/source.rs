@@ -0,0 +1,169 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Marketplace a listing comes from.
+///
+/// Parsing is case-insensitive and accepts common spellings such as
+/// `"mobile.de"` or `"mobile_de"`. Unknown names become `Source::Other`,
+/// so new sources can be used before this crate knows about them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Source {
+    /// encar.com (South Korea)
+    Encar,
+    /// mobile.de (Germany)
+    Mobilede,
+    /// autoscout24.com (Europe)
+    Autoscout24,
+    /// che168.com (China)
+    Che168,
+    /// dongchedi.com (China)
+    Dongchedi,
+    /// guazi.com (China)
+    Guazi,
+    /// dubicars.com (UAE)
+    Dubicars,
+    /// dubizzle.com (UAE)
+    Dubizzle,
+    /// A source not known to this version of the crate.
+    Other(String),
+}
+
+impl Source {
+    /// All sources known to this version of the crate.
+    pub const KNOWN: [Source; 8] = [
+        Source::Encar,
+        Source::Mobilede,
+        Source::Autoscout24,
+        Source::Che168,
+        Source::Dongchedi,
+        Source::Guazi,
+        Source::Dubicars,
+        Source::Dubizzle,
+    ];
+
+    /// Returns the identifier used in API paths (e.g. "mobilede").
+    pub fn as_str(&self) -> &str {
+        match self {
+            Source::Encar => "encar",
+            Source::Mobilede => "mobilede",
+            Source::Autoscout24 => "autoscout24",
+            Source::Che168 => "che168",
+            Source::Dongchedi => "dongchedi",
+            Source::Guazi => "guazi",
+            Source::Dubicars => "dubicars",
+            Source::Dubizzle => "dubizzle",
+            Source::Other(name) => name,
+        }
+    }
+
+    /// Returns true unless this is `Source::Other`.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Source::Other(_))
+    }
+
+    /// Returns the region the marketplace serves.
+    pub fn region(&self) -> Option<&'static str> {
+        match self {
+            Source::Encar => Some("South Korea"),
+            Source::Mobilede => Some("Germany"),
+            Source::Autoscout24 => Some("Europe"),
+            Source::Che168 | Source::Dongchedi | Source::Guazi => Some("China"),
+            Source::Dubicars | Source::Dubizzle => Some("UAE"),
+            Source::Other(_) => None,
+        }
+    }
+
+    /// Returns the ISO 4217 code of the currency prices are listed in.
+    pub fn default_currency(&self) -> Option<&'static str> {
+        match self {
+            Source::Encar => Some("KRW"),
+            Source::Mobilede | Source::Autoscout24 => Some("EUR"),
+            Source::Che168 | Source::Dongchedi | Source::Guazi => Some("CNY"),
+            Source::Dubicars | Source::Dubizzle => Some("AED"),
+            Source::Other(_) => None,
+        }
+    }
+
+    /// Returns the marketplace host name (e.g. "mobile.de").
+    pub fn host(&self) -> Option<&'static str> {
+        match self {
+            Source::Encar => Some("encar.com"),
+            Source::Mobilede => Some("mobile.de"),
+            Source::Autoscout24 => Some("autoscout24.com"),
+            Source::Che168 => Some("che168.com"),
+            Source::Dongchedi => Some("dongchedi.com"),
+            Source::Guazi => Some("guazi.com"),
+            Source::Dubicars => Some("dubicars.com"),
+            Source::Dubizzle => Some("dubizzle.com"),
+            Source::Other(_) => None,
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_lowercase();
+        Ok(match normalized.as_str() {
+            "encar" => Source::Encar,
+            "mobilede" | "mobile_de" | "mobile-de" | "mobile.de" => Source::Mobilede,
+            "autoscout24" | "autoscout" => Source::Autoscout24,
+            "che168" => Source::Che168,
+            "dongchedi" => Source::Dongchedi,
+            "guazi" => Source::Guazi,
+            "dubicars" => Source::Dubicars,
+            "dubizzle" => Source::Dubizzle,
+            _ => Source::Other(s.trim().to_string()),
+        })
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Source {
+    fn from(s: &str) -> Self {
+        match s.parse() {
+            Ok(source) => source,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl From<String> for Source {
+    fn from(s: String) -> Self {
+        Source::from(s.as_str())
+    }
+}
+
+impl From<&String> for Source {
+    fn from(s: &String) -> Self {
+        Source::from(s.as_str())
+    }
+}
+
+impl From<&Source> for Source {
+    fn from(source: &Source) -> Self {
+        source.clone()
+    }
+}
+
+impl Serialize for Source {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Source {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Source::from(s))
+    }
+}
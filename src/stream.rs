@@ -0,0 +1,396 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::source::Source;
+use crate::types::*;
+
+type PageFuture = Pin<Box<dyn Future<Output = Result<OffersResponse, Error>> + Send>>;
+
+/// Stream over every offer of a search, returned by [`Client::offers_stream`].
+///
+/// Follows `Meta.next_page` until the last page. Limits and prefetching are
+/// configured with chained calls before the stream is first polled:
+///
+/// ```no_run
+/// use auto_api_client::{Client, OffersParams};
+/// use futures_util::StreamExt;
+///
+/// # async fn run() -> Result<(), auto_api_client::Error> {
+/// let client = Client::new("your-api-key");
+/// let mut offers = client
+///     .offers_stream("encar", &OffersParams { brand: Some("BMW".into()), ..Default::default() })
+///     .max_pages(10)
+///     .prefetch(true);
+///
+/// while let Some(item) = offers.next().await {
+///     println!("{}", item?.inner_id);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct OffersStream {
+    client: Client,
+    source: Source,
+    params: OffersParams,
+    max_pages: Option<usize>,
+    max_items: Option<usize>,
+    prefetch: bool,
+    buffer: VecDeque<OfferItem>,
+    pending: Option<PageFuture>,
+    next_page: Option<i32>,
+    pages_requested: usize,
+    items_returned: usize,
+}
+
+impl OffersStream {
+    pub(crate) fn new(client: Client, source: Source, params: &OffersParams) -> Self {
+        Self {
+            client,
+            source,
+            params: params.clone(),
+            max_pages: None,
+            max_items: None,
+            prefetch: false,
+            buffer: VecDeque::new(),
+            pending: None,
+            next_page: Some(params.page.max(1)),
+            pages_requested: 0,
+            items_returned: 0,
+        }
+    }
+
+    /// Stops after fetching this many pages.
+    pub fn max_pages(mut self, max: usize) -> Self {
+        self.max_pages = Some(max);
+        self
+    }
+
+    /// Stops after yielding this many offers.
+    pub fn max_items(mut self, max: usize) -> Self {
+        self.max_items = Some(max);
+        self
+    }
+
+    /// Requests the next page while the current one is being consumed (default: false).
+    pub fn prefetch(mut self, prefetch: bool) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    fn can_request_page(&self) -> bool {
+        self.next_page.is_some() && self.max_pages.is_none_or(|max| self.pages_requested < max)
+    }
+
+    fn request_page(&mut self) {
+        let Some(page) = self.next_page.take() else {
+            return;
+        };
+        let client = self.client.clone();
+        let source = self.source.clone();
+        let params = OffersParams {
+            page,
+            ..self.params.clone()
+        };
+        // A locally sorted search returns all remaining pages at once, so
+        // the result set is fetched and sorted only once.
+        let pages = self
+            .max_pages
+            .map_or(usize::MAX, |max| max.saturating_sub(self.pages_requested));
+
+        self.pages_requested += 1;
+        self.pending = Some(Box::pin(async move {
+            client.offer_pages(&source, &params, pages).await
+        }));
+    }
+}
+
+impl Stream for OffersStream {
+    type Item = Result<OfferItem, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            if this.max_items.is_some_and(|max| this.items_returned >= max) {
+                this.buffer.clear();
+                this.pending = None;
+                this.next_page = None;
+                return Poll::Ready(None);
+            }
+
+            if this.pending.is_none()
+                && this.can_request_page()
+                && (this.buffer.is_empty() || this.prefetch)
+            {
+                this.request_page();
+            }
+
+            if let Some(pending) = this.pending.as_mut() {
+                match pending.as_mut().poll(cx) {
+                    Poll::Ready(Ok(response)) => {
+                        this.pending = None;
+                        if response.meta.next_page > 0
+                            && !response.result.is_empty()
+                            && !response.sorted_locally
+                        {
+                            this.next_page = Some(response.meta.next_page);
+                        }
+                        this.buffer.extend(response.result);
+                        continue;
+                    }
+                    Poll::Ready(Err(e)) => {
+                        this.pending = None;
+                        this.next_page = None;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    Poll::Pending => {
+                        if this.buffer.is_empty() {
+                            return Poll::Pending;
+                        }
+                    }
+                }
+            }
+
+            return match this.buffer.pop_front() {
+                Some(item) => {
+                    this.items_returned += 1;
+                    Poll::Ready(Some(Ok(item)))
+                }
+                None => Poll::Ready(None),
+            };
+        }
+    }
+}
+
+type ChangeIdFuture = Pin<Box<dyn Future<Output = Result<i64, Error>> + Send>>;
+type ChangesFuture = Pin<Box<dyn Future<Output = Result<ChangesResponse, Error>> + Send>>;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Position in a changes feed.
+///
+/// `offset` is the number of items of the batch at `change_id` that were
+/// already yielded, so resuming from a cursor skips exactly those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeCursor {
+    pub change_id: i64,
+    pub offset: usize,
+}
+
+impl ChangeCursor {
+    /// Returns a cursor at the start of the batch for `change_id`.
+    pub fn new(change_id: i64) -> Self {
+        Self {
+            change_id,
+            offset: 0,
+        }
+    }
+}
+
+/// Where a changes stream starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangesStart {
+    /// Start from a known change_id.
+    ChangeId(i64),
+    /// Start from the first change on a date (format: yyyy-mm-dd), resolved with `get_change_id`.
+    Date(String),
+    /// Resume from a cursor saved with [`ChangesStream::cursor`].
+    Cursor(ChangeCursor),
+}
+
+impl From<i64> for ChangesStart {
+    fn from(change_id: i64) -> Self {
+        ChangesStart::ChangeId(change_id)
+    }
+}
+
+impl From<&str> for ChangesStart {
+    /// Treats the string as a date (format: yyyy-mm-dd).
+    fn from(date: &str) -> Self {
+        ChangesStart::Date(date.to_string())
+    }
+}
+
+impl From<String> for ChangesStart {
+    /// Treats the string as a date (format: yyyy-mm-dd).
+    fn from(date: String) -> Self {
+        ChangesStart::Date(date)
+    }
+}
+
+impl From<ChangeCursor> for ChangesStart {
+    fn from(cursor: ChangeCursor) -> Self {
+        ChangesStart::Cursor(cursor)
+    }
+}
+
+enum ChangesState {
+    Idle,
+    Resolving(ChangeIdFuture),
+    Fetching(ChangesFuture),
+    Sleeping(futures_timer::Delay),
+}
+
+/// Endless stream over a changes feed, returned by [`Client::changes_stream`].
+///
+/// When a batch comes back empty and `next_change_id` hasn't moved on, the
+/// stream waits for the poll interval (default: 10 seconds) and asks again.
+/// Errors are yielded as items; polling again retries the same cursor after
+/// the poll interval.
+///
+/// ```no_run
+/// use auto_api_client::Client;
+/// use futures_util::StreamExt;
+///
+/// # async fn run() -> Result<(), auto_api_client::Error> {
+/// let client = Client::new("your-api-key");
+/// let mut changes = client.changes_stream("encar", "2025-01-15");
+///
+/// while let Some(change) = changes.next().await {
+///     println!("{} {}", change?.change_type, changes.cursor().unwrap().change_id);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChangesStream {
+    client: Client,
+    source: Source,
+    poll_interval: Duration,
+    date: Option<String>,
+    cursor: ChangeCursor,
+    batch_next: i64,
+    buffer: VecDeque<ChangeItem>,
+    state: ChangesState,
+}
+
+impl ChangesStream {
+    pub(crate) fn new(client: Client, source: Source, start: ChangesStart) -> Self {
+        let (date, cursor) = match start {
+            ChangesStart::ChangeId(change_id) => (None, ChangeCursor::new(change_id)),
+            ChangesStart::Date(date) => (Some(date), ChangeCursor::new(0)),
+            ChangesStart::Cursor(cursor) => (None, cursor),
+        };
+
+        Self {
+            client,
+            source,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            date,
+            cursor,
+            batch_next: 0,
+            buffer: VecDeque::new(),
+            state: ChangesState::Idle,
+        }
+    }
+
+    /// Sets how long to wait after an empty batch or an error (default: 10 seconds).
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Returns the position right after the last yielded item.
+    /// `None` until a start date has been resolved to a change_id.
+    pub fn cursor(&self) -> Option<ChangeCursor> {
+        if self.date.is_some() {
+            None
+        } else {
+            Some(self.cursor)
+        }
+    }
+
+    fn start_request(&mut self) {
+        let client = self.client.clone();
+        let source = self.source.clone();
+
+        self.state = match &self.date {
+            Some(date) => {
+                let date = date.clone();
+                ChangesState::Resolving(Box::pin(async move {
+                    client.get_change_id(&source, &date).await
+                }))
+            }
+            None => {
+                let change_id = self.cursor.change_id;
+                ChangesState::Fetching(Box::pin(async move {
+                    client.get_changes(&source, change_id).await
+                }))
+            }
+        };
+    }
+
+    fn sleep(&mut self) {
+        self.state = ChangesState::Sleeping(futures_timer::Delay::new(self.poll_interval));
+    }
+
+    fn accept_batch(&mut self, response: ChangesResponse) {
+        let next = response.meta.next_change_id;
+        self.buffer = response.result.into_iter().skip(self.cursor.offset).collect();
+
+        if !self.buffer.is_empty() {
+            self.batch_next = next;
+            self.state = ChangesState::Idle;
+        } else if next > self.cursor.change_id {
+            // Nothing to yield (an empty batch, or one consumed before a
+            // resume), but the feed moved on; fetch the next batch right away.
+            self.cursor = ChangeCursor::new(next);
+            self.state = ChangesState::Idle;
+        } else {
+            self.sleep();
+        }
+    }
+}
+
+impl Stream for ChangesStream {
+    type Item = Result<ChangeItem, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                this.cursor.offset += 1;
+                if this.buffer.is_empty() && this.batch_next > this.cursor.change_id {
+                    this.cursor = ChangeCursor::new(this.batch_next);
+                }
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            match &mut this.state {
+                ChangesState::Idle => this.start_request(),
+                ChangesState::Resolving(future) => match future.as_mut().poll(cx) {
+                    Poll::Ready(Ok(change_id)) => {
+                        this.date = None;
+                        this.cursor = ChangeCursor::new(change_id);
+                        this.state = ChangesState::Idle;
+                    }
+                    Poll::Ready(Err(e)) => {
+                        this.sleep();
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                ChangesState::Fetching(future) => match future.as_mut().poll(cx) {
+                    Poll::Ready(Ok(response)) => this.accept_batch(response),
+                    Poll::Ready(Err(e)) => {
+                        this.sleep();
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                ChangesState::Sleeping(delay) => match Pin::new(delay).poll(cx) {
+                    Poll::Ready(()) => this.state = ChangesState::Idle,
+                    Poll::Pending => return Poll::Pending,
+                },
+            }
+        }
+    }
+}
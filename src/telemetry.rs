@@ -0,0 +1,104 @@
+//! Request spans for the `tracing` feature. Without the feature everything
+//! here compiles to nothing.
+//!
+//! Spans never carry the URL or headers, so the API key can't leak into them.
+
+use std::time::Duration;
+
+use crate::error::Error;
+
+#[cfg(feature = "tracing")]
+pub(crate) use tracing::Span;
+
+/// Stand-in for `tracing::Span` when the feature is disabled.
+#[cfg(not(feature = "tracing"))]
+#[derive(Clone)]
+pub(crate) struct Span;
+
+/// Span around one API call, including its retries.
+#[cfg(feature = "tracing")]
+pub(crate) fn request_span(source: Option<&str>, endpoint: &str, query: &[(&str, &str)]) -> Span {
+    let param = |key: &str| query.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+    tracing::info_span!(
+        "auto_api.request",
+        source,
+        endpoint,
+        page = param("page"),
+        change_id = param("change_id"),
+        status = tracing::field::Empty,
+        latency_ms = tracing::field::Empty,
+        retries = tracing::field::Empty,
+        response_bytes = tracing::field::Empty,
+    )
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn request_span(_source: Option<&str>, _endpoint: &str, _query: &[(&str, &str)]) -> Span {
+    Span
+}
+
+/// Child span around reading and decoding a response.
+#[cfg(feature = "tracing")]
+pub(crate) fn response_span(parent: &Span, status: u16) -> Span {
+    tracing::debug_span!(parent: parent, "auto_api.handle_response", status, response_bytes = tracing::field::Empty)
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn response_span(_parent: &Span, _status: u16) -> Span {
+    Span
+}
+
+/// Runs `future` inside `span`.
+#[cfg(feature = "tracing")]
+pub(crate) fn instrument<F: std::future::Future>(future: F, span: Span) -> tracing::instrument::Instrumented<F> {
+    tracing::Instrument::instrument(future, span)
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn instrument<F: std::future::Future>(future: F, _span: Span) -> F {
+    future
+}
+
+/// Runs `f` inside `span`.
+#[cfg(feature = "tracing")]
+pub(crate) fn in_scope<T>(span: &Span, f: impl FnOnce() -> T) -> T {
+    span.in_scope(f)
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn in_scope<T>(_span: &Span, f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+/// Records a retry of the request in `span`.
+#[cfg(feature = "tracing")]
+pub(crate) fn record_retry(span: &Span, attempt: u32, delay: Duration) {
+    tracing::debug!(parent: span, attempt, delay_ms = delay.as_millis() as u64, "retrying request");
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn record_retry(_span: &Span, _attempt: u32, _delay: Duration) {}
+
+/// Records status and body size in the request span and the current response span.
+#[cfg(feature = "tracing")]
+pub(crate) fn record_response(span: &Span, status: u16, bytes: usize) {
+    span.record("status", status);
+    span.record("response_bytes", bytes);
+    Span::current().record("response_bytes", bytes);
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn record_response(_span: &Span, _status: u16, _bytes: usize) {}
+
+/// Records latency and retry count when the request is done, and the error if it failed.
+#[cfg(feature = "tracing")]
+pub(crate) fn record_outcome<T>(span: &Span, latency: Duration, retries: u32, result: &Result<T, Error>) {
+    span.record("latency_ms", latency.as_millis() as u64);
+    span.record("retries", retries);
+    if let Err(error) = result {
+        tracing::debug!(parent: span, %error, "request failed");
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn record_outcome<T>(_span: &Span, _latency: Duration, _retries: u32, _result: &Result<T, Error>) {}
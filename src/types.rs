@@ -1,31 +1,69 @@
-use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
+use crate::normalize::parse_timestamp;
+use crate::sort::{SortField, SortOrder};
+
 /// Parameters for `get_offers()`.
-/// Use `..Default::default()` for optional fields.
-#[derive(Debug, Default, Clone)]
+/// Use `..Default::default()` for optional fields, or [`OffersParams::builder`].
+///
+/// Serializes with unset fields omitted, so saved searches stay small;
+/// missing fields deserialize to their defaults.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OffersParams {
     pub page: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub brand: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub configuration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub complectation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transmission: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub engine_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub year_from: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub year_to: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mileage_from: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mileage_to: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub price_from: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub price_to: Option<i32>,
+    /// Sort field. See [`SortField`] for what happens when the server can't sort.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SortField>,
+    /// Sort direction (default: ascending).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+    /// Page size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    /// Additional source-specific query parameters (e.g. fuel, drive, region).
+    /// A key matching a built-in parameter replaces it.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
 }
 
 impl OffersParams {
     /// Converts parameters to a vector of (key, value) pairs for query string.
-    /// Only non-None values are included.
-    pub(crate) fn to_query_pairs(&self) -> Vec<(String, String)> {
+    /// Only non-None values are included, followed by `extra`.
+    pub fn to_query_pairs(&self) -> Vec<(String, String)> {
         let mut pairs = vec![("page".to_string(), self.page.to_string())];
 
         if let Some(ref v) = self.brand { pairs.push(("brand".into(), v.clone())); }
@@ -42,7 +80,12 @@ impl OffersParams {
         if let Some(v) = self.mileage_to { pairs.push(("mileage_to".into(), v.to_string())); }
         if let Some(v) = self.price_from { pairs.push(("price_from".into(), v.to_string())); }
         if let Some(v) = self.price_to { pairs.push(("price_to".into(), v.to_string())); }
+        if let Some(v) = self.sort { pairs.push(("sort".into(), v.as_str().into())); }
+        if let Some(v) = self.order { pairs.push(("order".into(), v.as_str().into())); }
+        if let Some(v) = self.limit { pairs.push(("limit".into(), v.to_string())); }
 
+        pairs.retain(|(k, _)| !self.extra.contains_key(k));
+        pairs.extend(self.extra.iter().map(|(k, v)| (k.clone(), v.clone())));
         pairs
     }
 }
@@ -52,6 +95,10 @@ impl OffersParams {
 pub struct OffersResponse {
     pub result: Vec<OfferItem>,
     pub meta: Meta,
+    /// True if the server ignored the requested sort order and the client
+    /// sorted and paged the full result set itself.
+    #[serde(skip)]
+    pub sorted_locally: bool,
 }
 
 /// Response from `get_changes()`.
@@ -83,7 +130,7 @@ pub struct ChangesMeta {
 pub struct OfferItem {
     pub id: i64,
     pub inner_id: String,
-    pub change_type: String,
+    pub change_type: ChangeType,
     pub created_at: String,
     pub data: Value,
 }
@@ -94,15 +141,107 @@ pub struct OfferItem {
 pub struct ChangeItem {
     pub id: i64,
     pub inner_id: String,
-    pub change_type: String,
+    pub change_type: ChangeType,
     pub created_at: String,
     pub data: Value,
 }
 
+impl OfferItem {
+    /// Parses `created_at`. Returns `None` if it isn't a recognised date or date-time.
+    pub fn created_time(&self) -> Option<SystemTime> {
+        parse_timestamp(&self.created_at)
+    }
+
+    /// Parses `created_at` as a UTC date-time (feature `chrono`).
+    #[cfg(feature = "chrono")]
+    pub fn created_at_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.created_time().map(Into::into)
+    }
+}
+
+impl ChangeItem {
+    /// Returns true if the offer was added.
+    pub fn is_added(&self) -> bool {
+        self.change_type == ChangeType::Added
+    }
+
+    /// Returns true if the offer was changed.
+    pub fn is_changed(&self) -> bool {
+        self.change_type == ChangeType::Changed
+    }
+
+    /// Returns true if the offer was removed.
+    pub fn is_removed(&self) -> bool {
+        self.change_type == ChangeType::Removed
+    }
+
+    /// Parses `created_at`. Returns `None` if it isn't a recognised date or date-time.
+    pub fn created_time(&self) -> Option<SystemTime> {
+        parse_timestamp(&self.created_at)
+    }
+
+    /// Parses `created_at` as a UTC date-time (feature `chrono`).
+    #[cfg(feature = "chrono")]
+    pub fn created_at_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.created_time().map(Into::into)
+    }
+}
+
+/// Kind of change reported for an offer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ChangeType {
+    Added,
+    Changed,
+    Removed,
+    /// Any other value, kept as sent by the API (may be empty).
+    Unknown(String),
+}
+
+impl ChangeType {
+    /// Returns the value as sent by the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ChangeType::Added => "added",
+            ChangeType::Changed => "changed",
+            ChangeType::Removed => "removed",
+            ChangeType::Unknown(s) => s,
+        }
+    }
+}
+
+impl From<&str> for ChangeType {
+    fn from(s: &str) -> Self {
+        match s {
+            "added" => ChangeType::Added,
+            "changed" => ChangeType::Changed,
+            "removed" => ChangeType::Removed,
+            other => ChangeType::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ChangeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ChangeType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ChangeType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(ChangeType::from(s.as_str()))
+    }
+}
+
 /// Common offer data fields shared across all sources.
-/// Since each source may have additional fields, deserialize from
-/// `OfferItem.data` into this or into your own struct.
-#[derive(Debug, Deserialize)]
+/// Fields a source adds beyond these are kept in `extra`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OfferData {
     pub inner_id: String,
     pub url: String,
@@ -124,6 +263,9 @@ pub struct OfferData {
     pub displacement: String,
     pub offer_created: String,
     pub images: Vec<String>,
+    /// Source-specific fields not modelled above.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
 }
 
 /// Response from `get_change_id()`.
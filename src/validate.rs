@@ -0,0 +1,135 @@
+use crate::error::{Error, ParamError};
+use crate::filters::Filters;
+use crate::types::OffersParams;
+
+impl OffersParams {
+    /// Checks brand, model, configuration and the other enumerated values
+    /// against a source's filters, that ranges aren't inverted and that
+    /// `limit` is positive.
+    ///
+    /// Values are compared ignoring case. Dimensions the filters don't list
+    /// are not checked. Returns `Error::InvalidParams` with every problem found,
+    /// including suggestions for near misses.
+    pub fn validate(&self, filters: &Filters) -> Result<(), Error> {
+        let mut errors = Vec::new();
+
+        if let Some(brand) = &self.brand {
+            let names: Vec<&str> = filters.brand_names().collect();
+            check(&mut errors, "brand", brand, &names);
+        }
+
+        let brand = self.brand.as_deref().and_then(|b| filters.brand(b));
+        if let (Some(model), Some(brand)) = (&self.model, brand) {
+            let names: Vec<&str> = brand.models.iter().map(|m| m.name.as_str()).collect();
+            check(&mut errors, "model", model, &names);
+        }
+
+        let model = brand.zip(self.model.as_deref()).and_then(|(b, m)| b.model(m));
+        if let (Some(configuration), Some(model)) = (&self.configuration, model) {
+            let names: Vec<&str> = model.configurations.iter().map(String::as_str).collect();
+            check(&mut errors, "configuration", configuration, &names);
+        }
+
+        let lists = [
+            ("body_type", &self.body_type, &filters.body_types),
+            ("transmission", &self.transmission, &filters.transmissions),
+            ("engine_type", &self.engine_type, &filters.engine_types),
+            ("color", &self.color, &filters.colors),
+        ];
+        for (field, value, allowed) in lists {
+            if let Some(value) = value {
+                let names: Vec<&str> = allowed.iter().map(String::as_str).collect();
+                check(&mut errors, field, value, &names);
+            }
+        }
+
+        let ranges = [
+            ("year_from", self.year_from, self.year_to),
+            ("mileage_from", self.mileage_from, self.mileage_to),
+            ("price_from", self.price_from, self.price_to),
+        ];
+        for (field, from, to) in ranges {
+            if let (Some(from), Some(to)) = (from, to) {
+                if from > to {
+                    errors.push(ParamError {
+                        field,
+                        value: from.to_string(),
+                        message: format!("greater than the upper bound {}", to),
+                        suggestion: None,
+                    });
+                }
+            }
+        }
+
+        if let Some(limit) = self.limit.filter(|limit| *limit < 1) {
+            errors.push(ParamError {
+                field: "limit",
+                value: limit.to_string(),
+                message: "must be at least 1".to_string(),
+                suggestion: None,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidParams(errors))
+        }
+    }
+}
+
+/// Records an error if `value` isn't one of `allowed`. An empty list means
+/// the source doesn't report this dimension, so anything is accepted.
+fn check(errors: &mut Vec<ParamError>, field: &'static str, value: &str, allowed: &[&str]) {
+    if allowed.is_empty() || allowed.iter().any(|a| a.eq_ignore_ascii_case(value)) {
+        return;
+    }
+
+    errors.push(ParamError {
+        field,
+        value: value.to_string(),
+        message: "not available for this source".to_string(),
+        suggestion: suggest(value, allowed).map(str::to_string),
+    });
+}
+
+/// Returns the closest allowed value: the shortest one starting with the
+/// input, or else the one within a small edit distance.
+fn suggest<'a>(value: &str, allowed: &[&'a str]) -> Option<&'a str> {
+    let needle = value.to_lowercase();
+    if needle.is_empty() {
+        return None;
+    }
+
+    if let Some(found) = allowed
+        .iter()
+        .filter(|a| a.to_lowercase().starts_with(&needle))
+        .min_by_key(|a| a.len())
+    {
+        return Some(found);
+    }
+
+    let max_distance = (needle.chars().count() / 3).max(2);
+    allowed
+        .iter()
+        .map(|a| (levenshtein(&needle, &a.to_lowercase()), *a))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, a)| a)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current[j + 1] = (previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
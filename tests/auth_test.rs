@@ -0,0 +1,150 @@
+use auto_api_client::{AuthMode, Client, Error, OffersParams};
+use mockito::{Matcher, ServerGuard};
+
+mod common;
+
+use common::OFFERS;
+
+async fn setup(mode: AuthMode) -> (ServerGuard, Client) {
+    let server = mockito::Server::new_async().await;
+    let client = Client::builder("test-key")
+        .base_url(&server.url())
+        .auth_mode(mode)
+        .build()
+        .unwrap();
+    (server, client)
+}
+
+// ── AuthMode::Query ─────────────────────────────────────────────
+
+#[test]
+fn test_query_mode_is_default() {
+    let client = Client::new("test-key");
+
+    assert!(format!("{:?}", client).contains("auth_mode: Query"));
+}
+
+#[tokio::test]
+async fn test_query_mode_sends_query_parameter_only() {
+    let (mut server, client) = setup(AuthMode::Query).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Exact("page=1&api_key=test-key".into()))
+        .match_header("x-api-key", Matcher::Missing)
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+
+    client
+        .get_offers("encar", &OffersParams { page: 1, ..Default::default() })
+        .await
+        .unwrap();
+
+    mock.assert();
+}
+
+// ── AuthMode::Header ────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_header_mode_sends_header_only() {
+    let (mut server, client) = setup(AuthMode::Header).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Exact("page=1".into()))
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+
+    client
+        .get_offers("encar", &OffersParams { page: 1, ..Default::default() })
+        .await
+        .unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_header_mode_on_change_id() {
+    let (mut server, client) = setup(AuthMode::Header).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/change_id")
+        .match_query(Matcher::Exact("date=2025-01-01".into()))
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_body(r#"{"change_id":5}"#)
+        .create();
+
+    client.get_change_id("encar", "2025-01-01").await.unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_header_mode_set_on_existing_client() {
+    let (mut server, mut client) = setup(AuthMode::Query).await;
+    client.set_auth_mode(AuthMode::Header);
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::Missing)
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    client.get_filters("encar").await.unwrap();
+
+    mock.assert();
+}
+
+// ── AuthMode::Both ──────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_both_mode_sends_query_parameter_and_header() {
+    let (mut server, client) = setup(AuthMode::Both).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/offer")
+        .match_query(Matcher::UrlEncoded("api_key".into(), "test-key".into()))
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+
+    client.get_offer("encar", "abc").await.unwrap();
+
+    mock.assert();
+}
+
+// ── POST ────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_offer_by_url_always_uses_header() {
+    let (mut server, client) = setup(AuthMode::Query).await;
+    let mock = server
+        .mock("POST", "/api/v1/offer/info")
+        .match_query(Matcher::Missing)
+        .match_header("x-api-key", "test-key")
+        .match_header("content-type", "application/json")
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    client.get_offer_by_url("https://encar.com/1").await.unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_key_invalid_as_header_is_credentials_error() {
+    let server = mockito::Server::new_async().await;
+    let client = Client::builder("bad\nkey")
+        .base_url(&server.url())
+        .auth_mode(AuthMode::Header)
+        .build()
+        .unwrap();
+
+    let err = client.get_filters("encar").await.unwrap_err();
+
+    assert!(matches!(err, Error::Credentials(_)), "got {:?}", err);
+    assert!(!err.to_string().contains("bad"));
+}
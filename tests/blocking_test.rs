@@ -0,0 +1,187 @@
+#![cfg(feature = "blocking")]
+
+use std::time::Duration;
+
+use auto_api_client::blocking::Client;
+use auto_api_client::{AuthMode, Error, KeyPool, OffersParams, RetryPolicy};
+use mockito::{Matcher, ServerGuard};
+
+fn setup() -> (ServerGuard, Client) {
+    let server = mockito::Server::new();
+    let mut client = Client::new("test-key");
+    client.set_base_url(server.url().as_str());
+    (server, client)
+}
+
+// ── endpoints ───────────────────────────────────────────────────
+
+#[test]
+fn test_get_filters() {
+    let (mut server, client) = setup();
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::Exact("api_key=test-key".into()))
+        .with_status(200)
+        .with_body(r#"{"brands":["BMW"]}"#)
+        .create();
+
+    let filters = client.get_filters("encar").unwrap();
+
+    mock.assert();
+    assert_eq!(filters["brands"][0], "BMW");
+}
+
+#[test]
+fn test_get_offers() {
+    let (mut server, client) = setup();
+    let mock = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("page".into(), "2".into()),
+            Matcher::UrlEncoded("brand".into(), "BMW".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"page":2,"next_page":3,"limit":20}}"#)
+        .create();
+    let params = OffersParams {
+        page: 2,
+        brand: Some("BMW".into()),
+        ..Default::default()
+    };
+
+    let offers = client.get_offers("encar", &params).unwrap();
+
+    mock.assert();
+    assert_eq!(offers.meta.next_page, 3);
+}
+
+#[test]
+fn test_get_offer_and_change_endpoints() {
+    let (mut server, client) = setup();
+    server
+        .mock("GET", "/api/v2/encar/offer")
+        .match_query(Matcher::UrlEncoded("inner_id".into(), "abc".into()))
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"page":1,"next_page":0,"limit":1}}"#)
+        .create();
+    server
+        .mock("GET", "/api/v2/encar/change_id")
+        .match_query(Matcher::UrlEncoded("date".into(), "2024-01-15".into()))
+        .with_status(200)
+        .with_body(r#"{"change_id":42}"#)
+        .create();
+    server
+        .mock("GET", "/api/v2/encar/changes")
+        .match_query(Matcher::UrlEncoded("change_id".into(), "42".into()))
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"cur_change_id":42,"next_change_id":43,"limit":20}}"#)
+        .create();
+
+    assert_eq!(client.get_offer("encar", "abc").unwrap().meta.limit, 1);
+    assert_eq!(client.get_change_id("encar", "2024-01-15").unwrap(), 42);
+    assert_eq!(client.get_changes("encar", 42).unwrap().meta.next_change_id, 43);
+}
+
+#[test]
+fn test_get_offer_by_url() {
+    let (mut server, client) = setup();
+    let mock = server
+        .mock("POST", "/api/v1/offer/info")
+        .match_header("x-api-key", "test-key")
+        .match_body(Matcher::JsonString(r#"{"url":"https://encar.com/1"}"#.into()))
+        .with_status(200)
+        .with_body(r#"{"id":1}"#)
+        .create();
+
+    let offer = client.get_offer_by_url("https://encar.com/1").unwrap();
+
+    mock.assert();
+    assert_eq!(offer["id"], 1);
+}
+
+#[test]
+fn test_header_auth_mode() {
+    let (mut server, mut client) = setup();
+    client.set_auth_mode(AuthMode::Header);
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::Missing)
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    client.get_filters("encar").unwrap();
+
+    mock.assert();
+}
+
+// ── errors and retries ──────────────────────────────────────────
+
+#[test]
+fn test_errors_match_async_client() {
+    let (mut server, client) = setup();
+    server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::Any)
+        .with_status(429)
+        .with_header("retry-after", "7")
+        .with_body(r#"{"message":"slow down"}"#)
+        .create();
+    server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"result":"nope"}"#)
+        .create();
+
+    match client.get_filters("encar") {
+        Err(Error::RateLimited { retry_after, message }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(7)));
+            assert_eq!(message, "slow down");
+        }
+        other => panic!("expected RateLimited, got {:?}", other),
+    }
+    match client.get_offers("encar", &OffersParams::default()) {
+        Err(Error::Decode { path, .. }) => assert_eq!(path, "result"),
+        other => panic!("expected Decode, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_retries_and_rotates_keys() {
+    let mut server = mockito::Server::new();
+    let mut client = Client::with_credentials(KeyPool::new(["bad-key", "good-key"])).unwrap();
+    client.set_base_url(server.url().as_str());
+    client.set_retry_policy(RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    });
+    let unavailable = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::UrlEncoded("api_key".into(), "bad-key".into()))
+        .with_status(503)
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::UrlEncoded("api_key".into(), "good-key".into()))
+        .with_status(200)
+        .with_body("{}")
+        .expect(1)
+        .create();
+
+    client.get_filters("encar").unwrap();
+
+    unavailable.assert();
+    ok.assert();
+}
+
+#[test]
+fn test_debug_redacts_key() {
+    let client = Client::new("secret-key-123");
+
+    assert!(!format!("{:?}", client).contains("secret-key-123"));
+}
@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+
+use auto_api_client::{
+    ChangeCursor, ChangesStart, CheckpointStore, Error, JsonFileCheckpointStore,
+    MemoryCheckpointStore,
+};
+
+mod common;
+
+use common::{changes_mock, setup};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("auto-api-{}-{}.json", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+// ── Stores ──────────────────────────────────────────────────────
+
+#[test]
+fn test_memory_store_round_trip() {
+    let store = MemoryCheckpointStore::new();
+
+    assert_eq!(store.load("encar").unwrap(), None);
+    store.save("encar", ChangeCursor::new(42)).unwrap();
+
+    assert_eq!(store.load("encar").unwrap(), Some(ChangeCursor::new(42)));
+    assert_eq!(store.load("che168").unwrap(), None);
+}
+
+#[test]
+fn test_json_file_store_persists_across_instances() {
+    let path = temp_path("persist");
+
+    JsonFileCheckpointStore::new(&path)
+        .save("encar", ChangeCursor { change_id: 42, offset: 3 })
+        .unwrap();
+    JsonFileCheckpointStore::new(&path)
+        .save("che168", ChangeCursor::new(7))
+        .unwrap();
+
+    let store = JsonFileCheckpointStore::new(&path);
+    assert_eq!(store.load("encar").unwrap(), Some(ChangeCursor { change_id: 42, offset: 3 }));
+    assert_eq!(store.load("che168").unwrap(), Some(ChangeCursor::new(7)));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_json_file_store_reports_corrupt_file() {
+    let path = temp_path("corrupt");
+    std::fs::write(&path, "not json").unwrap();
+
+    let result = JsonFileCheckpointStore::new(&path).load("encar");
+
+    assert!(matches!(result, Err(Error::Checkpoint(_))));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_store_round_trip() {
+    let store = auto_api_client::SqliteCheckpointStore::from_connection(
+        rusqlite::Connection::open_in_memory().unwrap(),
+    )
+    .unwrap();
+
+    store.save("encar", ChangeCursor::new(1)).unwrap();
+    store.save("encar", ChangeCursor { change_id: 2, offset: 5 }).unwrap();
+
+    assert_eq!(store.load("encar").unwrap(), Some(ChangeCursor { change_id: 2, offset: 5 }));
+    assert_eq!(store.load("guazi").unwrap(), None);
+}
+
+// ── sync_changes ────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_sync_changes_commits_after_each_batch() {
+    let (mut server, client) = setup().await;
+    let _batch1 = changes_mock(&mut server, 100, &["a1", "a2"], 102);
+    let _batch2 = changes_mock(&mut server, 102, &["b1"], 103);
+    let _batch3 = changes_mock(&mut server, 103, &[], 103);
+    let store = MemoryCheckpointStore::new();
+    let mut seen = Vec::new();
+
+    let cursor = client
+        .sync_changes("encar", &store, 100, |items| {
+            seen.push(items.len());
+            async { Ok::<_, Error>(()) }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(seen, vec![2, 1]);
+    assert_eq!(cursor, ChangeCursor::new(103));
+    assert_eq!(store.load("encar").unwrap(), Some(ChangeCursor::new(103)));
+}
+
+#[tokio::test]
+async fn test_sync_changes_continues_past_empty_batch_that_moved_on() {
+    let (mut server, client) = setup().await;
+    let _batch1 = changes_mock(&mut server, 100, &[], 105);
+    let _batch2 = changes_mock(&mut server, 105, &["a1"], 106);
+    let _batch3 = changes_mock(&mut server, 106, &[], 106);
+    let store = MemoryCheckpointStore::new();
+    let mut seen = Vec::new();
+
+    let cursor = client
+        .sync_changes("encar", &store, 100, |items| {
+            seen.push(items.len());
+            async { Ok::<_, Error>(()) }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(seen, vec![1]);
+    assert_eq!(cursor, ChangeCursor::new(106));
+}
+
+#[tokio::test]
+async fn test_sync_changes_resumes_from_saved_cursor() {
+    let (mut server, client) = setup().await;
+    let resolve = server
+        .mock("GET", "/api/v2/encar/change_id")
+        .match_query(mockito::Matcher::Any)
+        .expect(0)
+        .create();
+    let _batch = changes_mock(&mut server, 102, &["b1", "b2"], 104);
+    let _empty = changes_mock(&mut server, 104, &[], 104);
+    let store = MemoryCheckpointStore::new();
+    store.save("encar", ChangeCursor { change_id: 102, offset: 1 }).unwrap();
+    let mut ids = Vec::new();
+
+    client
+        .sync_changes("encar", &store, ChangesStart::Date("2024-01-01".into()), |items| {
+            ids.extend(items.into_iter().map(|i| i.inner_id));
+            async { Ok::<_, Error>(()) }
+        })
+        .await
+        .unwrap();
+
+    resolve.assert();
+    assert_eq!(ids, vec!["b2"]);
+}
+
+#[tokio::test]
+async fn test_sync_changes_does_not_commit_failed_batch() {
+    let (mut server, client) = setup().await;
+    let _batch1 = changes_mock(&mut server, 100, &["a1"], 101);
+    let _batch2 = changes_mock(&mut server, 101, &["b1"], 102);
+    let store = MemoryCheckpointStore::new();
+
+    let result = client
+        .sync_changes("encar", &store, 100, |items| {
+            let fail = items[0].inner_id == "b1";
+            async move {
+                if fail {
+                    Err(Error::Checkpoint("handler failed".into()))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(store.load("encar").unwrap(), Some(ChangeCursor::new(101)));
+}
+
+#[tokio::test]
+async fn test_sync_changes_with_json_file_store() {
+    let (mut server, client) = setup().await;
+    let _batch = changes_mock(&mut server, 100, &["a1"], 101);
+    let _empty = changes_mock(&mut server, 101, &[], 101);
+    let path = temp_path("sync");
+    let store = JsonFileCheckpointStore::new(&path);
+
+    client
+        .sync_changes("encar", &store, 100, |_| async { Ok::<_, Error>(()) })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        JsonFileCheckpointStore::new(&path).load("encar").unwrap(),
+        Some(ChangeCursor::new(101))
+    );
+    std::fs::remove_file(&path).unwrap();
+}
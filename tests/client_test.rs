@@ -1,5 +1,8 @@
-use auto_api_client::{Client, Error, OffersParams};
+use std::time::Duration;
+
+use auto_api_client::{Client, Error, OffersParams, Source};
 use mockito::{Mock, ServerGuard};
+use reqwest::header::{HeaderMap, HeaderValue};
 
 async fn setup() -> (ServerGuard, Client) {
     let server = mockito::Server::new_async().await;
@@ -85,7 +88,7 @@ async fn test_get_offers_returns_data() {
 async fn test_get_offers_with_filters() {
     let (mut server, client) = setup().await;
     let mock = server
-        .mock("GET", "/api/v2/mobile_de/offers")
+        .mock("GET", "/api/v2/mobilede/offers")
         .match_query(mockito::Matcher::AllOf(vec![
             mockito::Matcher::UrlEncoded("page".into(), "2".into()),
             mockito::Matcher::UrlEncoded("brand".into(), "BMW".into()),
@@ -96,7 +99,7 @@ async fn test_get_offers_with_filters() {
 
     client
         .get_offers(
-            "mobile_de",
+            Source::Mobilede,
             &OffersParams {
                 page: 2,
                 brand: Some("BMW".into()),
@@ -292,6 +295,85 @@ async fn test_custom_api_version() {
     mock.assert();
 }
 
+// ── ClientBuilder ───────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_builder_sets_base_url_and_version() {
+    let mut server = mockito::Server::new_async().await;
+    let client = Client::builder("test-key")
+        .base_url(&format!("{}/", server.url()))
+        .api_version("v3")
+        .build()
+        .unwrap();
+    let mock = json_mock(&mut server, "GET", "/api/v3/encar/filters", 200, "{}");
+
+    client.get_filters("encar").await.unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_builder_sends_user_agent_and_default_headers() {
+    let mut server = mockito::Server::new_async().await;
+    let mut headers = HeaderMap::new();
+    headers.insert("x-team", HeaderValue::from_static("sync"));
+    let client = Client::builder("test-key")
+        .base_url(&server.url())
+        .user_agent("sync-job/1.0")
+        .default_headers(headers)
+        .connect_timeout(Duration::from_secs(5))
+        .read_timeout(Duration::from_secs(5))
+        .pool_max_idle_per_host(4)
+        .build()
+        .unwrap();
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .match_header("user-agent", "sync-job/1.0")
+        .match_header("x-team", "sync")
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    client.get_filters("encar").await.unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_builder_uses_supplied_http_client() {
+    let mut server = mockito::Server::new_async().await;
+    let mut headers = HeaderMap::new();
+    headers.insert("x-custom", HeaderValue::from_static("yes"));
+    let http_client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+    let client = Client::builder("test-key")
+        .base_url(&server.url())
+        .http_client(http_client)
+        .build()
+        .unwrap();
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .match_header("x-custom", "yes")
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    client.get_filters("encar").await.unwrap();
+
+    mock.assert();
+}
+
+#[test]
+fn test_builder_returns_error_instead_of_panicking() {
+    let result = Client::builder("test-key").user_agent("bad\nagent").build();
+
+    assert!(matches!(result, Err(Error::Network(_))));
+}
+
 // ── Error handling ──────────────────────────────────────────────
 
 #[tokio::test]
@@ -356,7 +438,7 @@ async fn test_api_error_fallback_message() {
 }
 
 #[tokio::test]
-async fn test_api_error_contains_body() {
+async fn test_invalid_request_contains_body() {
     let (mut server, client) = setup().await;
     let response_body = r#"{"message":"Validation failed","errors":["invalid"]}"#;
     let mock = json_mock(
@@ -371,8 +453,11 @@ async fn test_api_error_contains_body() {
 
     mock.assert();
     match result.unwrap_err() {
-        Error::Api { body, .. } => assert!(body.contains("Validation failed")),
-        other => panic!("expected Error::Api, got {:?}", other),
+        Error::InvalidRequest { status_code, body, .. } => {
+            assert_eq!(status_code, 422);
+            assert!(body.contains("Validation failed"));
+        }
+        other => panic!("expected Error::InvalidRequest, got {:?}", other),
     }
 }
 
@@ -419,7 +504,7 @@ async fn test_auth_error_on_403() {
 }
 
 #[tokio::test]
-async fn test_invalid_json_returns_api_error() {
+async fn test_invalid_json_returns_decode_error() {
     let (mut server, client) = setup().await;
     let mock = server
         .mock("GET", "/api/v2/encar/filters")
@@ -432,13 +517,16 @@ async fn test_invalid_json_returns_api_error() {
 
     mock.assert();
     match result.unwrap_err() {
-        Error::Api { message, .. } => assert!(message.contains("Invalid JSON response")),
-        other => panic!("expected Error::Api, got {:?}", other),
+        Error::Decode { path, snippet, .. } => {
+            assert_eq!(path, ".");
+            assert_eq!(snippet, "not json at all");
+        }
+        other => panic!("expected Error::Decode, got {:?}", other),
     }
 }
 
 #[tokio::test]
-async fn test_404_returns_api_error_not_auth() {
+async fn test_404_returns_not_found() {
     let (mut server, client) = setup().await;
     let mock = json_mock(
         &mut server,
@@ -452,11 +540,130 @@ async fn test_404_returns_api_error_not_auth() {
 
     mock.assert();
     match result.unwrap_err() {
-        Error::Api { status_code, .. } => assert_eq!(status_code, 404),
-        other => panic!("expected Error::Api, got {:?}", other),
+        Error::NotFound { message } => assert_eq!(message, "Source not found"),
+        other => panic!("expected Error::NotFound, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_429_returns_rate_limited_with_retry_after() {
+    let (mut server, client) = setup().await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(429)
+        .with_header("retry-after", "5")
+        .with_body(r#"{"message":"Slow down"}"#)
+        .create();
+
+    let result = client.get_filters("encar").await;
+
+    mock.assert();
+    let err = result.unwrap_err();
+    assert!(err.is_retryable());
+    match err {
+        Error::RateLimited { retry_after, message } => {
+            assert_eq!(retry_after, Some(Duration::from_secs(5)));
+            assert_eq!(message, "Slow down");
+        }
+        other => panic!("expected Error::RateLimited, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_decode_error_reports_path() {
+    let (mut server, client) = setup().await;
+    let mock = json_mock(
+        &mut server,
+        "GET",
+        "/api/v2/encar/changes",
+        200,
+        r#"{"result":[{"id":"not-a-number","inner_id":"a","change_type":"added","created_at":"","data":{}}],"meta":{"cur_change_id":1,"next_change_id":2,"limit":20}}"#,
+    );
+
+    let result = client.get_changes("encar", 1).await;
+
+    mock.assert();
+    match result.unwrap_err() {
+        Error::Decode { path, snippet, .. } => {
+            assert_eq!(path, "result[0].id");
+            assert!(snippet.contains("not-a-number"));
+        }
+        other => panic!("expected Error::Decode, got {:?}", other),
     }
 }
 
+#[tokio::test]
+async fn test_timeout_returns_timeout_error() {
+    // Accepts connections but never answers.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = Client::builder("test-key")
+        .base_url(&format!("http://{}", listener.local_addr().unwrap()))
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    let err = client.get_filters("encar").await.unwrap_err();
+
+    assert!(matches!(err, Error::Timeout(_)), "got {:?}", err);
+    assert!(err.is_retryable());
+}
+
+#[test]
+fn test_is_retryable_classification() {
+    let api = |status_code| Error::Api {
+        status_code,
+        message: String::new(),
+        body: String::new(),
+    };
+
+    assert!(api(503).is_retryable());
+    assert!(!api(501).is_retryable());
+    assert!(!Error::NotFound { message: String::new() }.is_retryable());
+    assert!(!Error::Auth { status_code: 401, message: String::new() }.is_retryable());
+    assert!(!Error::InvalidRequest {
+        status_code: 400,
+        message: String::new(),
+        body: String::new(),
+    }
+    .is_retryable());
+}
+
+// ── Redaction ───────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_network_error_does_not_contain_api_key() {
+    let client = Client::builder("secret-key-123")
+        .base_url("http://127.0.0.1:1")
+        .build()
+        .unwrap();
+
+    let err = client
+        .get_offers("encar", &OffersParams { page: 2, ..Default::default() })
+        .await
+        .unwrap_err();
+
+    let Error::Network(inner) = &err else {
+        panic!("expected Error::Network, got {:?}", err);
+    };
+    let url = inner.url().unwrap();
+    assert_eq!(url.query(), Some("page=2"));
+    assert!(!err.to_string().contains("secret-key-123"));
+    assert!(!format!("{:?}", err).contains("secret-key-123"));
+}
+
+#[test]
+fn test_debug_output_redacts_api_key() {
+    let builder = Client::builder("secret-key-123").base_url("http://localhost");
+    let builder_debug = format!("{:?}", builder);
+    let client_debug = format!("{:?}", builder.build().unwrap());
+
+    assert!(!builder_debug.contains("secret-key-123"));
+    assert!(!client_debug.contains("secret-key-123"));
+    assert!(client_debug.contains("[redacted]"));
+    assert!(client_debug.contains("http://localhost"));
+}
+
 // ── Error Display ───────────────────────────────────────────────
 
 #[test]
@@ -477,3 +684,22 @@ fn test_error_display_api() {
     };
     assert_eq!(format!("{}", err), "API error 500: Server error");
 }
+
+#[test]
+fn test_error_display_rate_limited() {
+    let err = Error::RateLimited {
+        retry_after: Some(Duration::from_secs(30)),
+        message: "Too many requests".into(),
+    };
+    assert_eq!(format!("{}", err), "rate limited (retry after 30s): Too many requests");
+}
+
+#[test]
+fn test_error_display_decode() {
+    let err = Error::Decode {
+        path: "meta.limit".into(),
+        message: "invalid type".into(),
+        snippet: r#""limit":"x""#.into(),
+    };
+    assert_eq!(format!("{}", err), r#"decode error at meta.limit: invalid type (near "\"limit\":\"x\"")"#);
+}
@@ -0,0 +1,49 @@
+//! Mockito fixtures shared by the integration tests.
+// Each test crate uses only some of these.
+#![allow(dead_code)]
+
+use auto_api_client::{Client, ClientBuilder};
+use mockito::{Mock, ServerGuard};
+
+/// An empty, single-page offers response.
+pub const OFFERS: &str = r#"{"result":[],"meta":{"page":1,"next_page":0,"limit":20}}"#;
+
+pub async fn setup() -> (ServerGuard, Client) {
+    let server = mockito::Server::new_async().await;
+    let mut client = Client::new("test-key");
+    client.set_base_url(server.url().as_str());
+    (server, client)
+}
+
+/// Like [`setup`], with the client configured by `configure`.
+pub async fn setup_with(configure: impl FnOnce(ClientBuilder) -> ClientBuilder) -> (ServerGuard, Client) {
+    let server = mockito::Server::new_async().await;
+    let client = configure(Client::builder("test-key").base_url(&server.url()))
+        .build()
+        .unwrap();
+    (server, client)
+}
+
+pub fn changes_mock(server: &mut ServerGuard, change_id: i64, ids: &[&str], next_change_id: i64) -> Mock {
+    let items: Vec<String> = ids
+        .iter()
+        .map(|id| {
+            format!(
+                r#"{{"id":1,"inner_id":"{}","change_type":"added","created_at":"2024-01-15","data":{{}}}}"#,
+                id
+            )
+        })
+        .collect();
+    let body = format!(
+        r#"{{"result":[{}],"meta":{{"cur_change_id":{},"next_change_id":{},"limit":500}}}}"#,
+        items.join(","),
+        change_id,
+        next_change_id
+    );
+    server
+        .mock("GET", "/api/v2/encar/changes")
+        .match_query(mockito::Matcher::UrlEncoded("change_id".into(), change_id.to_string()))
+        .with_status(200)
+        .with_body(body)
+        .create()
+}
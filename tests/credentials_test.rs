@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use auto_api_client::{
+    Client, ClientBuilder, CredentialsProvider, EnvCredentials, Error, FileCredentials, KeyPool,
+    StaticCredentials,
+};
+use mockito::{Matcher, Mock, ServerGuard};
+
+async fn setup(credentials: impl CredentialsProvider + 'static) -> (ServerGuard, Client) {
+    let server = mockito::Server::new_async().await;
+    let client = ClientBuilder::with_credentials(credentials)
+        .base_url(&server.url())
+        .filters_ttl(std::time::Duration::ZERO)
+        .build()
+        .unwrap();
+    (server, client)
+}
+
+fn key_mock(server: &mut ServerGuard, key: &str, status: usize) -> Mock {
+    server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::UrlEncoded("api_key".into(), key.into()))
+        .with_status(status)
+        .with_body(r#"{"message":"checked"}"#)
+        .create()
+}
+
+// ── StaticCredentials / EnvCredentials ──────────────────────────
+
+#[test]
+fn test_static_credentials_debug_is_redacted() {
+    let credentials = StaticCredentials::new("secret-key-123");
+
+    assert_eq!(credentials.api_key().unwrap(), "secret-key-123");
+    assert!(!format!("{:?}", credentials).contains("secret-key-123"));
+}
+
+#[test]
+fn test_env_credentials_read_on_every_call() {
+    let var = "AUTO_API_CLIENT_TEST_KEY";
+    let credentials = EnvCredentials::new(var);
+
+    std::env::remove_var(var);
+    assert!(matches!(credentials.api_key(), Err(Error::Credentials(_))));
+
+    std::env::set_var(var, " key-1 ");
+    assert_eq!(credentials.api_key().unwrap(), "key-1");
+
+    std::env::set_var(var, "key-2");
+    assert_eq!(credentials.api_key().unwrap(), "key-2");
+    std::env::remove_var(var);
+}
+
+// ── FileCredentials ─────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_file_credentials_reload_when_file_changes() {
+    let path = std::env::temp_dir().join(format!("auto-api-key-{}", std::process::id()));
+    std::fs::write(&path, "first-key\n").unwrap();
+    let (mut server, client) = setup(FileCredentials::new(&path)).await;
+    let first = key_mock(&mut server, "first-key", 200);
+    let second = key_mock(&mut server, "second-key-rotated", 200).expect(2);
+
+    client.get_filters("encar").await.unwrap();
+    std::fs::write(&path, "second-key-rotated\n").unwrap();
+    client.get_filters("encar").await.unwrap();
+    // A missing file during replacement keeps the last key.
+    std::fs::remove_file(&path).unwrap();
+    client.get_filters("encar").await.unwrap();
+
+    first.assert();
+    second.assert();
+}
+
+#[test]
+fn test_file_credentials_missing_file() {
+    let credentials = FileCredentials::new("/nonexistent/auto-api-key");
+
+    assert!(matches!(credentials.api_key(), Err(Error::Credentials(_))));
+}
+
+// ── KeyPool ─────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_key_pool_rotates_round_robin() {
+    let (mut server, client) = setup(KeyPool::new(["key-a", "key-b"])).await;
+    let a = key_mock(&mut server, "key-a", 200).expect(2);
+    let b = key_mock(&mut server, "key-b", 200).expect(2);
+
+    for _ in 0..4 {
+        client.get_filters("encar").await.unwrap();
+    }
+
+    a.assert();
+    b.assert();
+}
+
+#[tokio::test]
+async fn test_key_pool_drops_rejected_key() {
+    let pool = Arc::new(KeyPool::new(["key-a", "key-b"]));
+    let (mut server, client) = setup(pool.clone()).await;
+    let a = key_mock(&mut server, "key-a", 401).expect(1);
+    let b = key_mock(&mut server, "key-b", 200).expect(3);
+
+    let first = client.get_filters("encar").await;
+    for _ in 0..3 {
+        client.get_filters("encar").await.unwrap();
+    }
+
+    assert!(matches!(first, Err(Error::Auth { .. })));
+    assert_eq!(pool.available(), 1);
+    a.assert();
+    b.assert();
+}
+
+#[tokio::test]
+async fn test_key_pool_fails_once_all_keys_are_rejected() {
+    let pool = Arc::new(KeyPool::new(["key-a"]));
+    let (mut server, client) = setup(pool.clone()).await;
+    let a = key_mock(&mut server, "key-a", 403).expect(1);
+
+    let first = client.get_filters("encar").await;
+    let second = client.get_filters("encar").await;
+
+    a.assert();
+    assert!(matches!(first, Err(Error::Auth { .. })));
+    assert!(matches!(second, Err(Error::Credentials(_))));
+    assert_eq!(pool.available(), 0);
+}
+
+#[test]
+fn test_key_pool_debug_is_redacted() {
+    let pool = KeyPool::new(["secret-a", "secret-b"]);
+
+    let debug = format!("{:?}", pool);
+
+    assert!(!debug.contains("secret-a"));
+    assert!(!debug.contains("secret-b"));
+}
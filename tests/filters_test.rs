@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use auto_api_client::{Client, Filters};
+use serde_json::json;
+
+// ── Filters::from_value ─────────────────────────────────────────
+
+#[test]
+fn test_filters_from_flat_lists() {
+    let filters = Filters::from_value(json!({
+        "brands": ["Toyota", "Honda"],
+        "body_types": ["sedan", "suv"],
+        "transmissions": [{"name": "automatic"}, {"name": "manual"}],
+        "engine_types": ["petrol", "diesel"],
+    }));
+
+    assert_eq!(filters.brand_names().collect::<Vec<_>>(), vec!["Toyota", "Honda"]);
+    assert_eq!(filters.body_types, vec!["sedan", "suv"]);
+    assert_eq!(filters.transmissions, vec!["automatic", "manual"]);
+    assert_eq!(filters.engine_types, vec!["petrol", "diesel"]);
+    assert!(filters.colors.is_empty());
+    assert!(filters.brand("Toyota").unwrap().models.is_empty());
+}
+
+#[test]
+fn test_filters_from_nested_maps() {
+    let filters = Filters::from_value(json!({
+        "mark": {
+            "BMW": {"X5": ["E70", "F15"], "3 Series": ["G20"]},
+            "Kia": {"Rio": []},
+        }
+    }));
+
+    let bmw = filters.brand("bmw").unwrap();
+    assert_eq!(bmw.name, "BMW");
+    assert_eq!(bmw.model("x5").unwrap().configurations, vec!["E70", "F15"]);
+    assert_eq!(filters.models("Kia").unwrap()[0].name, "Rio");
+}
+
+#[test]
+fn test_filters_from_object_lists() {
+    let filters = Filters::from_value(json!({
+        "brands": [
+            {"name": "Hyundai", "models": [{"name": "Sonata", "configurations": ["DN8"]}]},
+            {"name": "Genesis"},
+        ],
+        "colors": {"white": 10, "black": 5},
+    }));
+
+    assert_eq!(filters.models("Hyundai").unwrap()[0].configurations, vec!["DN8"]);
+    assert!(filters.models("Genesis").unwrap().is_empty());
+    assert_eq!(filters.colors.len(), 2);
+    assert!(filters.brand("Lada").is_none());
+}
+
+#[test]
+fn test_filters_keep_raw_value() {
+    let raw = json!({"brands": [], "seller_types": ["dealer"]});
+
+    let filters = Filters::from_value(raw.clone());
+
+    assert_eq!(filters.raw, raw);
+    assert_eq!(filters.raw["seller_types"][0], "dealer");
+}
+
+// ── get_filters_typed ───────────────────────────────────────────
+
+#[tokio::test]
+async fn test_get_filters_typed() {
+    let mut server = mockito::Server::new_async().await;
+    let mut client = Client::new("test-key");
+    client.set_base_url(server.url().as_str());
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"brands":{"Hyundai":{"Sonata":["DN8"]}},"body_types":["sedan"]}"#)
+        .create();
+
+    let filters = client.get_filters_typed("encar").await.unwrap();
+
+    mock.assert();
+    assert_eq!(filters.brand("Hyundai").unwrap().model("Sonata").unwrap().configurations, vec!["DN8"]);
+    assert_eq!(filters.body_types, vec!["sedan"]);
+}
+
+// ── filters cache ───────────────────────────────────────────────
+
+fn filters_mock(server: &mut mockito::ServerGuard, body: &str) -> mockito::Mock {
+    server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(body)
+        .expect(1)
+        .create()
+}
+
+async fn cached_client(builder: auto_api_client::ClientBuilder) -> (mockito::ServerGuard, Client) {
+    let server = mockito::Server::new_async().await;
+    let client = builder.base_url(server.url().as_str()).build().unwrap();
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_filters_are_cached() {
+    let (mut server, client) = cached_client(Client::builder("test-key")).await;
+    let mock = filters_mock(&mut server, r#"{"brands":["Kia"]}"#);
+
+    let raw = client.get_filters("encar").await.unwrap();
+    let typed = client.clone().get_filters_typed("encar").await.unwrap();
+
+    mock.assert();
+    assert_eq!(raw, typed.raw);
+    assert_eq!(typed.brand_names().collect::<Vec<_>>(), vec!["Kia"]);
+}
+
+#[tokio::test]
+async fn test_invalidate_filters_reloads() {
+    let (mut server, client) = cached_client(Client::builder("test-key")).await;
+    let first = filters_mock(&mut server, r#"{"brands":["Kia"]}"#);
+    let second = filters_mock(&mut server, r#"{"brands":["Kia","Hyundai"]}"#);
+
+    client.get_filters_typed("encar").await.unwrap();
+    client.invalidate_filters("encar");
+    let filters = client.get_filters_typed("encar").await.unwrap();
+
+    first.assert();
+    second.assert();
+    assert_eq!(filters.brands.len(), 2);
+}
+
+#[tokio::test]
+async fn test_zero_ttl_disables_cache() {
+    let (mut server, client) =
+        cached_client(Client::builder("test-key").filters_ttl(Duration::ZERO)).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body("{}")
+        .expect(2)
+        .create();
+
+    client.get_filters("encar").await.unwrap();
+    client.get_filters("encar").await.unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_expired_filters_reload_without_spawner() {
+    let (mut server, client) =
+        cached_client(Client::builder("test-key").filters_ttl(Duration::from_millis(20))).await;
+    let first = filters_mock(&mut server, r#"{"brands":["Kia"]}"#);
+    let second = filters_mock(&mut server, r#"{"brands":["Kia","Hyundai"]}"#);
+
+    client.get_filters_typed("encar").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let filters = client.get_filters_typed("encar").await.unwrap();
+
+    first.assert();
+    second.assert();
+    assert_eq!(filters.brands.len(), 2);
+}
+
+#[tokio::test]
+async fn test_expired_filters_refresh_in_background() {
+    let builder = Client::builder("test-key")
+        .filters_ttl(Duration::from_millis(20))
+        .spawner(|future| {
+            tokio::spawn(future);
+        });
+    let (mut server, client) = cached_client(builder).await;
+    let first = filters_mock(&mut server, r#"{"brands":["Kia"]}"#);
+    let second = filters_mock(&mut server, r#"{"brands":["Kia","Hyundai"]}"#);
+
+    client.get_filters_typed("encar").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // The stale value is served while the refresh runs.
+    let stale = client.get_filters_typed("encar").await.unwrap();
+    assert_eq!(stale.brands.len(), 1);
+
+    let mut refreshed = stale;
+    for _ in 0..50 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        refreshed = client.get_filters_typed("encar").await.unwrap();
+        if refreshed.brands.len() == 2 {
+            break;
+        }
+    }
+
+    first.assert();
+    second.assert();
+    assert_eq!(refreshed.brands.len(), 2);
+}
@@ -0,0 +1,241 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use auto_api_client::{
+    Client, Error, MetricsRecorder, OffersParams, Outcome, PrometheusRecorder, RequestMetrics,
+    RetryPolicy,
+};
+use mockito::{Matcher, ServerGuard};
+
+/// A finished request as seen by the recorder.
+#[derive(Debug, Clone, PartialEq)]
+struct Recorded {
+    source: Option<String>,
+    endpoint: String,
+    outcome: Outcome,
+    status: Option<u16>,
+    bytes: u64,
+}
+
+#[derive(Debug, Default)]
+struct TestRecorder {
+    requests: Mutex<Vec<Recorded>>,
+    items: Mutex<Vec<(String, String, usize)>>,
+}
+
+impl MetricsRecorder for TestRecorder {
+    fn record_request(&self, request: &RequestMetrics<'_>) {
+        self.requests.lock().unwrap().push(Recorded {
+            source: request.source.map(str::to_string),
+            endpoint: request.endpoint.to_string(),
+            outcome: request.outcome,
+            status: request.status,
+            bytes: request.bytes,
+        });
+    }
+
+    fn record_items(&self, source: &str, endpoint: &str, items: usize) {
+        self.items
+            .lock()
+            .unwrap()
+            .push((source.to_string(), endpoint.to_string(), items));
+    }
+}
+
+async fn setup(recorder: impl MetricsRecorder + 'static) -> (ServerGuard, Client) {
+    let server = mockito::Server::new_async().await;
+    let client = Client::builder("test-key")
+        .base_url(&server.url())
+        .metrics(recorder)
+        .build()
+        .unwrap();
+    (server, client)
+}
+
+const OFFERS: &str = r#"{"result":[{"id":1,"inner_id":"a","change_type":"added","created_at":"2024-01-15","data":{}},{"id":2,"inner_id":"b","change_type":"added","created_at":"2024-01-15","data":{}}],"meta":{"page":1,"next_page":0,"limit":20}}"#;
+
+// ── MetricsRecorder ─────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_records_successful_request_and_items() {
+    let recorder = Arc::new(TestRecorder::default());
+    let (mut server, client) = setup(recorder.clone()).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+
+    client.get_offers("encar", &OffersParams::default()).await.unwrap();
+
+    mock.assert();
+    assert_eq!(
+        *recorder.requests.lock().unwrap(),
+        vec![Recorded {
+            source: Some("encar".into()),
+            endpoint: "offers".into(),
+            outcome: Outcome::Ok,
+            status: Some(200),
+            bytes: OFFERS.len() as u64,
+        }]
+    );
+    assert_eq!(
+        *recorder.items.lock().unwrap(),
+        vec![("encar".to_string(), "offers".to_string(), 2)]
+    );
+}
+
+#[tokio::test]
+async fn test_records_changes_items() {
+    let recorder = Arc::new(TestRecorder::default());
+    let (mut server, client) = setup(recorder.clone()).await;
+    server
+        .mock("GET", "/api/v2/encar/changes")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"cur_change_id":1,"next_change_id":1,"limit":20}}"#)
+        .create();
+
+    client.get_changes("encar", 1).await.unwrap();
+
+    assert_eq!(
+        *recorder.items.lock().unwrap(),
+        vec![("encar".to_string(), "changes".to_string(), 0)]
+    );
+}
+
+#[tokio::test]
+async fn test_records_error_outcomes() {
+    let recorder = Arc::new(TestRecorder::default());
+    let (mut server, client) = setup(recorder.clone()).await;
+    server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::Any)
+        .with_status(401)
+        .with_body(r#"{"message":"bad key"}"#)
+        .create();
+    server
+        .mock("POST", "/api/v1/offer/info")
+        .with_status(500)
+        .create();
+
+    assert!(client.get_filters("encar").await.is_err());
+    assert!(client.get_offer_by_url("https://encar.com/1").await.is_err());
+
+    let requests = recorder.requests.lock().unwrap();
+    assert_eq!(requests[0].outcome, Outcome::Auth);
+    assert_eq!(requests[0].status, Some(401));
+    assert_eq!(requests[1].source, None);
+    assert_eq!(requests[1].endpoint, "offer/info");
+    assert_eq!(requests[1].outcome, Outcome::Api);
+    assert!(recorder.items.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_records_network_outcome() {
+    let recorder = Arc::new(TestRecorder::default());
+    let client = Client::builder("test-key")
+        .base_url("http://127.0.0.1:1")
+        .metrics(recorder.clone())
+        .build()
+        .unwrap();
+
+    let result = client.get_filters("encar").await;
+
+    assert!(matches!(result, Err(Error::Network(_))));
+    let requests = recorder.requests.lock().unwrap();
+    assert_eq!(requests[0].outcome, Outcome::Network);
+    assert_eq!(requests[0].status, None);
+    assert_eq!(requests[0].bytes, 0);
+}
+
+#[tokio::test]
+async fn test_network_error_after_retry_reports_no_status() {
+    // Answers the first connection with a 503 and closes the second unanswered.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let responses = [
+            "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 4\r\nconnection: close\r\n\r\nbusy",
+            "",
+        ];
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0u8; 4096]);
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    let recorder = Arc::new(TestRecorder::default());
+    let client = Client::builder("test-key")
+        .base_url(&url)
+        .retry_policy(RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::from_millis(1),
+            ..Default::default()
+        })
+        .metrics(recorder.clone())
+        .build()
+        .unwrap();
+
+    let result = client.get_filters("encar").await;
+
+    assert!(matches!(result, Err(Error::Network(_))), "got {:?}", result);
+    let requests = recorder.requests.lock().unwrap();
+    assert_eq!(requests[0].outcome, Outcome::Network);
+    assert_eq!(requests[0].status, None);
+    assert_eq!(requests[0].bytes, 0);
+}
+
+#[test]
+fn test_outcome_of_results() {
+    let auth: Result<(), Error> = Err(Error::Credentials("missing".into()));
+    let api: Result<(), Error> = Err(Error::NotFound { message: "gone".into() });
+
+    assert_eq!(Outcome::of(&Ok::<_, Error>(())), Outcome::Ok);
+    assert_eq!(Outcome::of(&auth), Outcome::Auth);
+    assert_eq!(Outcome::of(&api), Outcome::Api);
+    assert_eq!(Outcome::Network.to_string(), "network");
+}
+
+// ── PrometheusRecorder ──────────────────────────────────────────
+
+#[tokio::test]
+async fn test_prometheus_render() {
+    let metrics = PrometheusRecorder::new();
+    let (mut server, client) = setup(metrics.clone()).await;
+    server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+
+    client.get_offers("encar", &OffersParams::default()).await.unwrap();
+    client.get_offers("encar", &OffersParams::default()).await.unwrap();
+    let text = metrics.render();
+
+    assert!(text.contains("# TYPE auto_api_requests_total counter\n"));
+    assert!(text.contains(r#"auto_api_requests_total{source="encar",endpoint="offers",outcome="ok"} 2"#));
+    assert!(text.contains(r#"auto_api_request_duration_seconds_count{source="encar",endpoint="offers"} 2"#));
+    assert!(text.contains(&format!(
+        r#"auto_api_response_bytes_total{{source="encar",endpoint="offers"}} {}"#,
+        OFFERS.len() * 2
+    )));
+    assert!(text.contains(r#"auto_api_page_items_bucket{source="encar",endpoint="offers",le="1"} 0"#));
+    assert!(text.contains(r#"auto_api_page_items_bucket{source="encar",endpoint="offers",le="10"} 2"#));
+    assert!(text.contains(r#"auto_api_page_items_sum{source="encar",endpoint="offers"} 4"#));
+}
+
+#[test]
+fn test_prometheus_escapes_labels() {
+    let metrics = PrometheusRecorder::new();
+
+    metrics.record_items("we\"ird", "offers", 3);
+
+    assert!(metrics
+        .render()
+        .contains(r#"auto_api_page_items_count{source="we\"ird",endpoint="offers"} 1"#));
+}
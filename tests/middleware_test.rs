@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use auto_api_client::{Middleware, OffersParams, RawResponse};
+use mockito::Matcher;
+use reqwest::header::HeaderValue;
+
+mod common;
+
+use common::{setup_with, OFFERS};
+
+#[derive(Debug)]
+struct AddHeader(&'static str, &'static str);
+
+impl Middleware for AddHeader {
+    fn on_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+        request
+            .headers_mut()
+            .insert(self.0, HeaderValue::from_static(self.1));
+        None
+    }
+}
+
+/// Answers repeated GETs from memory.
+#[derive(Debug, Default)]
+struct Cache(Mutex<HashMap<String, RawResponse>>);
+
+impl Middleware for Cache {
+    fn on_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+        self.0.lock().unwrap().get(request.url().as_str()).cloned()
+    }
+
+    fn on_response(&self, response: &mut RawResponse) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(response.url.to_string(), response.clone());
+    }
+}
+
+/// Records hook calls as `"<name>:request"` / `"<name>:response"`.
+#[derive(Debug)]
+struct Trace(&'static str, Arc<Mutex<Vec<String>>>);
+
+impl Middleware for Trace {
+    fn on_request(&self, _request: &mut reqwest::Request) -> Option<RawResponse> {
+        self.1.lock().unwrap().push(format!("{}:request", self.0));
+        None
+    }
+
+    fn on_response(&self, _response: &mut RawResponse) {
+        self.1.lock().unwrap().push(format!("{}:response", self.0));
+    }
+}
+
+// ── requests ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_middleware_adds_header_to_get_and_post() {
+    let (mut server, client) = setup_with(|b| b.middleware(AddHeader("x-tenant", "acme"))).await;
+    let get = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Any)
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+    let post = server
+        .mock("POST", "/api/v1/offer/info")
+        .match_header("x-tenant", "acme")
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    client.get_offers("encar", &OffersParams::default()).await.unwrap();
+    client.get_offer_by_url("https://encar.com/1").await.unwrap();
+
+    get.assert();
+    post.assert();
+}
+
+#[derive(Debug)]
+struct Proxy;
+
+impl Middleware for Proxy {
+    fn on_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+        let path = format!("/proxy{}", request.url().path());
+        request.url_mut().set_path(&path);
+        None
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_rewrites_url() {
+    let (mut server, client) = setup_with(|b| b.middleware(Proxy)).await;
+    let mock = server
+        .mock("GET", "/proxy/api/v2/encar/offers")
+        .match_query(Matcher::UrlEncoded("api_key".into(), "test-key".into()))
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+
+    client.get_offers("encar", &OffersParams::default()).await.unwrap();
+
+    mock.assert();
+}
+
+// ── responses ───────────────────────────────────────────────────
+
+#[derive(Debug)]
+struct Unwrap;
+
+impl Middleware for Unwrap {
+    fn on_response(&self, response: &mut RawResponse) {
+        if let Some(inner) = response.body.strip_prefix("wrapped:") {
+            response.body = inner.to_string();
+            response.status = 200;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_alters_response() {
+    let (mut server, client) = setup_with(|b| b.middleware(Unwrap)).await;
+    server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Any)
+        .with_status(502)
+        .with_body(format!("wrapped:{}", OFFERS))
+        .create();
+
+    let response = client.get_offers("encar", &OffersParams::default()).await.unwrap();
+
+    assert_eq!(response.meta.limit, 20);
+}
+
+#[tokio::test]
+async fn test_middleware_short_circuits_with_cached_response() {
+    let (mut server, client) = setup_with(|b| b.middleware(Cache::default())).await;
+    let first = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+        .with_status(200)
+        .with_body(OFFERS)
+        .expect(1)
+        .create();
+    let second = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_status(200)
+        .with_body(OFFERS)
+        .expect(1)
+        .create();
+
+    client.get_offers("encar", &OffersParams { page: 1, ..Default::default() }).await.unwrap();
+    let cached = client
+        .get_offers("encar", &OffersParams { page: 1, ..Default::default() })
+        .await
+        .unwrap();
+    client
+        .get_offers("encar", &OffersParams { page: 2, ..Default::default() })
+        .await
+        .unwrap();
+
+    assert_eq!(cached.meta.page, 1);
+    first.assert();
+    second.assert();
+}
+
+#[tokio::test]
+async fn test_middleware_order() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let (mut server, client) = setup_with(|b| {
+        b.middleware(Trace("outer", calls.clone()))
+            .middleware(Cache::default())
+            .middleware(Trace("inner", calls.clone()))
+    })
+    .await;
+    server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    client.get_filters("encar").await.unwrap();
+    client.invalidate_filters("encar");
+    client.get_filters("encar").await.unwrap();
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        [
+            "outer:request",
+            "inner:request",
+            "inner:response",
+            "outer:response",
+            // answered by the cache: later middleware is skipped
+            "outer:request",
+            "outer:response",
+        ]
+    );
+}
@@ -0,0 +1,105 @@
+use auto_api_client::{OfferItem, Source, TypedOffer};
+use serde_json::{json, Value};
+
+fn item(data: Value) -> OfferItem {
+    serde_json::from_value(json!({
+        "id": 1,
+        "inner_id": "a1",
+        "change_type": "added",
+        "created_at": "2024-01-15",
+        "data": data,
+    }))
+    .unwrap()
+}
+
+/// Complete offer data with `fields` added or overridden.
+#[allow(dead_code)]
+fn data(fields: Value) -> Value {
+    let mut data = json!({
+        "inner_id": "1", "url": "", "mark": "BMW", "model": "X5", "generation": "",
+        "configuration": "", "complectation": "", "year": "2020", "color": "",
+        "price": "1000", "km_age": "0", "engine_type": "", "transmission_type": "",
+        "body_type": "", "address": "", "seller_type": "", "is_dealer": false,
+        "displacement": "", "offer_created": "", "images": [],
+    });
+    if let (Some(data), Value::Object(fields)) = (data.as_object_mut(), fields) {
+        data.extend(fields);
+    }
+    data
+}
+
+// ── TypedOffer ──────────────────────────────────────────────────
+
+#[test]
+fn test_unknown_source_decodes_to_other() {
+    let data = json!({"mark": "BMW"});
+
+    let decoded = item(data.clone()).decode(Source::Other("carsales".into())).unwrap();
+
+    assert_eq!(decoded, TypedOffer::Other(data));
+}
+
+#[cfg(feature = "encar")]
+#[test]
+fn test_encar_offer_keeps_unknown_fields_in_extra() {
+    let decoded = item(data(json!({
+        "inner_id": "40427050",
+        "mark": "Hyundai",
+        "is_dealer": true,
+        "images": ["a.jpg"],
+        "inspection": {"accident": false},
+    })))
+    .decode("encar")
+    .unwrap();
+
+    match decoded {
+        TypedOffer::Encar(offer) => {
+            assert_eq!(offer.mark, "Hyundai");
+            assert!(offer.is_dealer);
+            assert_eq!(offer.images, vec!["a.jpg"]);
+            assert_eq!(offer.extra.len(), 1);
+            assert_eq!(offer.extra["inspection"]["accident"], false);
+        }
+        other => panic!("expected TypedOffer::Encar, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "encar")]
+#[test]
+fn test_encar_offer_rejects_missing_fields() {
+    let result = item(json!({"mark": "Hyundai", "price": "2500"})).decode("encar");
+
+    assert!(result.unwrap_err().to_string().contains("missing field"));
+}
+
+#[cfg(feature = "mobilede")]
+#[test]
+fn test_mobilede_offer_round_trips_extra_fields() {
+    let decoded = item(data(json!({"mark": "BMW", "vat_deductible": true})))
+        .decode(Source::Mobilede)
+        .unwrap();
+
+    match decoded {
+        TypedOffer::Mobilede(offer) => {
+            let value = serde_json::to_value(&offer).unwrap();
+            assert_eq!(value["vat_deductible"], true);
+            assert_eq!(value["mark"], "BMW");
+        }
+        other => panic!("expected TypedOffer::Mobilede, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "che168")]
+#[test]
+fn test_change_item_decodes_by_source() {
+    let change: auto_api_client::ChangeItem = serde_json::from_value(json!({
+        "id": 1,
+        "inner_id": "c1",
+        "change_type": "changed",
+        "created_at": "2024-01-15",
+        "data": data(json!({"mark": "BYD"})),
+    }))
+    .unwrap();
+
+    assert!(matches!(change.decode(Source::Che168).unwrap(), TypedOffer::Che168(o) if o.mark == "BYD"));
+}
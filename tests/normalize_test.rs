@@ -0,0 +1,85 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use auto_api_client::{Money, OfferData, Source};
+use serde_json::json;
+
+fn offer(price: &str, km_age: &str, year: &str, displacement: &str, offer_created: &str) -> OfferData {
+    serde_json::from_value(json!({
+        "inner_id": "1", "url": "", "mark": "BMW", "model": "X5", "generation": "",
+        "configuration": "", "complectation": "", "year": year, "color": "",
+        "price": price, "km_age": km_age, "engine_type": "", "transmission_type": "",
+        "body_type": "", "address": "", "seller_type": "", "is_dealer": false,
+        "displacement": displacement, "offer_created": offer_created, "images": [],
+    }))
+    .unwrap()
+}
+
+// ── normalized ──────────────────────────────────────────────────
+
+#[test]
+fn test_normalized_parses_plain_values() {
+    let n = offer("45000", "12345", "2020", "1998", "2024-01-15").normalized(Source::Mobilede);
+
+    assert_eq!(
+        n.price,
+        Ok(Money { amount: 45000.0, currency: Some("EUR".into()) })
+    );
+    assert_eq!(n.mileage_km, Ok(12345));
+    assert_eq!(n.year, Ok(2020));
+    assert_eq!(n.displacement_cc, Ok(Some(1998)));
+    assert_eq!(n.offer_created, Ok(UNIX_EPOCH + Duration::from_secs(1_705_276_800)));
+    assert!(n.errors().is_empty());
+}
+
+#[test]
+fn test_normalized_handles_separators_units_and_symbols() {
+    let n = offer("$25,000.50", "120 000 km", "2018", "2.0 L", "2024-01-15T10:30:00Z").normalized("encar");
+
+    assert_eq!(
+        n.price,
+        Ok(Money { amount: 25000.5, currency: Some("USD".into()) })
+    );
+    assert_eq!(n.mileage_km, Ok(120_000));
+    assert_eq!(n.displacement_cc, Ok(Some(2000)));
+    assert_eq!(
+        n.offer_created,
+        Ok(UNIX_EPOCH + Duration::from_secs(1_705_276_800 + 10 * 3600 + 30 * 60))
+    );
+}
+
+#[test]
+fn test_normalized_european_thousands_separator() {
+    let n = offer("25.000 €", "0", "2022", "", "2024-01-15 12:00:00+02:00").normalized("che168");
+
+    assert_eq!(
+        n.price,
+        Ok(Money { amount: 25000.0, currency: Some("EUR".into()) })
+    );
+    assert_eq!(n.displacement_cc, Ok(None));
+    assert_eq!(
+        n.offer_created,
+        Ok(UNIX_EPOCH + Duration::from_secs(1_705_276_800 + 10 * 3600))
+    );
+}
+
+#[test]
+fn test_normalized_reports_errors_per_field() {
+    let n = offer("on request", "12345", "unknown", "1598", "yesterday").normalized("encar");
+
+    assert_eq!(n.mileage_km, Ok(12345));
+    assert_eq!(n.displacement_cc, Ok(Some(1598)));
+    let fields: Vec<&str> = n.errors().iter().map(|e| e.field).collect();
+    assert_eq!(fields, vec!["price", "year", "offer_created"]);
+    assert_eq!(
+        n.price.unwrap_err().to_string(),
+        r#"invalid price "on request": expected a number"#
+    );
+}
+
+#[test]
+fn test_normalized_unknown_source_has_no_default_currency() {
+    let n = offer("1000", "1", "2020", "", "1705276800").normalized("carsales");
+
+    assert_eq!(n.price, Ok(Money { amount: 1000.0, currency: None }));
+    assert_eq!(n.offer_created, Ok(UNIX_EPOCH + Duration::from_secs(1_705_276_800)));
+}
@@ -0,0 +1,200 @@
+use auto_api_client::{Client, Error, OffersParams, SortField, SortOrder};
+use mockito::Matcher;
+
+fn pairs(params: &OffersParams) -> Vec<(String, String)> {
+    params.to_query_pairs()
+}
+
+// ── OffersParams::builder ───────────────────────────────────────
+
+#[test]
+fn test_builder_sets_fields() {
+    let params = OffersParams::builder()
+        .page(2)
+        .brand("BMW")
+        .model("X5".to_string())
+        .body_type("suv")
+        .build();
+
+    assert_eq!(
+        params,
+        OffersParams {
+            page: 2,
+            brand: Some("BMW".into()),
+            model: Some("X5".into()),
+            body_type: Some("suv".into()),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_builder_range_helpers() {
+    let params = OffersParams::builder()
+        .year(2018..=2022)
+        .mileage(..100_000)
+        .price(10_000..)
+        .build();
+
+    assert_eq!((params.year_from, params.year_to), (Some(2018), Some(2022)));
+    assert_eq!((params.mileage_from, params.mileage_to), (None, Some(99_999)));
+    assert_eq!((params.price_from, params.price_to), (Some(10_000), None));
+}
+
+#[test]
+fn test_builder_range_replaces_both_bounds() {
+    let params = OffersParams::builder().year(2018..=2022).year(2020..).build();
+
+    assert_eq!((params.year_from, params.year_to), (Some(2020), None));
+}
+
+// ── extra parameters ────────────────────────────────────────────
+
+#[test]
+fn test_extra_params_are_appended() {
+    let params = OffersParams::builder()
+        .page(1)
+        .brand("BMW")
+        .extra("fuel", "diesel")
+        .extra("seller_type", "dealer")
+        .extra("limit", 50)
+        .build();
+
+    assert_eq!(
+        pairs(&params),
+        vec![
+            ("page".to_string(), "1".to_string()),
+            ("brand".into(), "BMW".into()),
+            ("fuel".into(), "diesel".into()),
+            ("limit".into(), "50".into()),
+            ("seller_type".into(), "dealer".into()),
+        ]
+    );
+}
+
+#[test]
+fn test_extra_param_replaces_builtin() {
+    let params = OffersParams::builder().page(1).brand("BMW").extra("brand", "bmw").build();
+
+    assert_eq!(
+        pairs(&params),
+        vec![("page".to_string(), "1".to_string()), ("brand".into(), "bmw".into())]
+    );
+}
+
+#[tokio::test]
+async fn test_get_offers_sends_extra_params() {
+    let mut server = mockito::Server::new_async().await;
+    let mut client = Client::new("test-key");
+    client.set_base_url(server.url().as_str());
+    let mock = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("brand".into(), "Kia".into()),
+            Matcher::UrlEncoded("drive".into(), "awd".into()),
+            Matcher::UrlEncoded("year_to".into(), "2021".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"page":1,"next_page":0,"limit":20}}"#)
+        .create();
+
+    let params = OffersParams::builder()
+        .page(1)
+        .brand("Kia")
+        .year(..2022)
+        .extra("drive", "awd")
+        .build();
+    client.get_offers("encar", &params).await.unwrap();
+
+    mock.assert();
+}
+
+// ── serde ───────────────────────────────────────────────────────
+
+#[test]
+fn test_params_serde_round_trip() {
+    let params = OffersParams::builder()
+        .page(1)
+        .brand("BMW")
+        .year(2018..=2022)
+        .sort(SortField::Created, SortOrder::Desc)
+        .extra("fuel", "diesel")
+        .build();
+
+    let json = serde_json::to_value(&params).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "page": 1,
+            "brand": "BMW",
+            "year_from": 2018,
+            "year_to": 2022,
+            "sort": "created",
+            "order": "desc",
+            "extra": {"fuel": "diesel"},
+        })
+    );
+    assert_eq!(serde_json::from_value::<OffersParams>(json).unwrap(), params);
+}
+
+#[test]
+fn test_params_deserialize_missing_fields_as_defaults() {
+    let params: OffersParams = serde_json::from_str(r#"{"brand":"Kia"}"#).unwrap();
+
+    assert_eq!(
+        params,
+        OffersParams {
+            brand: Some("Kia".into()),
+            ..Default::default()
+        }
+    );
+}
+
+// ── query strings ───────────────────────────────────────────────
+
+#[test]
+fn test_query_string_matches_query_pairs() {
+    let params = OffersParams::builder()
+        .page(3)
+        .brand("Mercedes-Benz")
+        .model("E Class")
+        .price(..50000)
+        .limit(10)
+        .extra("region", "Baden-Württemberg")
+        .build();
+
+    let query = params.to_query_string();
+
+    assert_eq!(
+        query,
+        "page=3&brand=Mercedes-Benz&model=E+Class&price_to=49999&limit=10&region=Baden-W%C3%BCrttemberg"
+    );
+    let parsed = OffersParams::from_query_string(&query).unwrap();
+    assert_eq!(parsed, params);
+    assert_eq!(parsed.to_query_pairs(), params.to_query_pairs());
+}
+
+#[test]
+fn test_from_query_string_accepts_url_query() {
+    let params =
+        OffersParams::from_query_string("?page=1&sort=price&order=asc&api_key=secret&drive=awd").unwrap();
+
+    assert_eq!(params.sort, Some(SortField::Price));
+    assert_eq!(params.order, Some(SortOrder::Asc));
+    assert_eq!(params.extra.get("drive").map(String::as_str), Some("awd"));
+    assert!(!params.extra.contains_key("api_key"));
+}
+
+#[test]
+fn test_from_query_string_reports_invalid_values() {
+    let result = OffersParams::from_query_string("page=one&year_from=2018&price_to=cheap&sort=rating");
+
+    match result {
+        Err(Error::InvalidParams(errors)) => {
+            let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+            assert_eq!(fields, vec!["page", "price_to", "sort"]);
+        }
+        other => panic!("expected InvalidParams, got {:?}", other),
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use auto_api_client::{Client, ClientBuilder, RateLimit};
+use mockito::ServerGuard;
+
+mod common;
+
+async fn setup(configure: impl FnOnce(ClientBuilder) -> ClientBuilder) -> (ServerGuard, Client) {
+    // Every call must reach the server, so the filters cache is disabled.
+    common::setup_with(|b| configure(b.filters_ttl(Duration::ZERO))).await
+}
+
+fn filters_mock(server: &mut ServerGuard, source: &str) -> mockito::Mock {
+    server
+        .mock("GET", format!("/api/v2/{}/filters", source).as_str())
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body("{}")
+        .create()
+}
+
+fn per_second(rate: f64) -> RateLimit {
+    RateLimit {
+        requests_per_second: Some(rate),
+        burst: 1,
+        ..Default::default()
+    }
+}
+
+// ── Token bucket ────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_rate_limit_spaces_requests() {
+    let (mut server, client) = setup(|b| b.rate_limit(per_second(10.0))).await;
+    let _mock = filters_mock(&mut server, "encar");
+
+    let started = Instant::now();
+    for _ in 0..3 {
+        client.get_filters("encar").await.unwrap();
+    }
+
+    assert!(started.elapsed() >= Duration::from_millis(190));
+}
+
+#[tokio::test]
+async fn test_rate_limit_allows_burst() {
+    let limit = RateLimit {
+        requests_per_second: Some(1.0),
+        burst: 3,
+        ..Default::default()
+    };
+    let (mut server, client) = setup(|b| b.rate_limit(limit)).await;
+    let _mock = filters_mock(&mut server, "encar");
+
+    let started = Instant::now();
+    for _ in 0..3 {
+        client.get_filters("encar").await.unwrap();
+    }
+
+    assert!(started.elapsed() < Duration::from_millis(900));
+}
+
+#[tokio::test]
+async fn test_rate_limit_is_shared_across_clones() {
+    let (mut server, client) = setup(|b| b.rate_limit(per_second(5.0))).await;
+    let _mock = filters_mock(&mut server, "encar");
+    let clone = client.clone();
+
+    let started = Instant::now();
+    client.get_filters("encar").await.unwrap();
+    clone.get_filters("encar").await.unwrap();
+
+    assert!(started.elapsed() >= Duration::from_millis(190));
+}
+
+#[tokio::test]
+async fn test_source_rate_limit_applies_only_to_that_source() {
+    let (mut server, client) = setup(|b| b.source_rate_limit("encar", per_second(2.0))).await;
+    let _encar = filters_mock(&mut server, "encar");
+    let _che168 = filters_mock(&mut server, "che168");
+
+    client.get_filters("encar").await.unwrap();
+    let started = Instant::now();
+    for _ in 0..3 {
+        client.get_filters("che168").await.unwrap();
+    }
+    assert!(started.elapsed() < Duration::from_millis(400));
+
+    let started = Instant::now();
+    client.get_filters("encar").await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(400));
+}
+
+// ── Concurrency cap ─────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_max_in_flight_caps_parallel_requests() {
+    let limit = RateLimit {
+        max_in_flight: Some(1),
+        ..Default::default()
+    };
+    let (mut server, client) = setup(|b| b.rate_limit(limit)).await;
+    let current = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let (c, p) = (current.clone(), peak.clone());
+    let _mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body_from_request(move |_| {
+            let now = c.fetch_add(1, Ordering::SeqCst) + 1;
+            p.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(30));
+            c.fetch_sub(1, Ordering::SeqCst);
+            b"{}".to_vec()
+        })
+        .create();
+
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.get_filters("encar").await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    assert_eq!(peak.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,305 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::time::{Duration, Instant};
+
+use auto_api_client::{Client, Error, RetryPolicy};
+use mockito::ServerGuard;
+
+fn fast_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        ..Default::default()
+    }
+}
+
+async fn setup(policy: RetryPolicy) -> (ServerGuard, Client) {
+    let server = mockito::Server::new_async().await;
+    let client = Client::builder("test-key")
+        .base_url(&server.url())
+        .retry_policy(policy)
+        .build()
+        .unwrap();
+    (server, client)
+}
+
+// ── Retryable statuses ──────────────────────────────────────────
+
+#[tokio::test]
+async fn test_retries_502_then_succeeds() {
+    let (mut server, client) = setup(fast_policy(3)).await;
+    let failing = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(502)
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"brands":["BMW"]}"#)
+        .expect(1)
+        .create();
+
+    let result = client.get_filters("encar").await.unwrap();
+
+    failing.assert();
+    ok.assert();
+    assert_eq!(result["brands"][0], "BMW");
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let (mut server, client) = setup(fast_policy(3)).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(503)
+        .expect(3)
+        .create();
+
+    let result = client.get_filters("encar").await;
+
+    mock.assert();
+    match result.unwrap_err() {
+        Error::Api { status_code, .. } => assert_eq!(status_code, 503),
+        other => panic!("expected Error::Api, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_does_not_retry_non_retryable_status() {
+    let (mut server, client) = setup(fast_policy(3)).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(400)
+        .expect(1)
+        .create();
+
+    let result = client.get_filters("encar").await;
+
+    mock.assert();
+    assert!(matches!(result.unwrap_err(), Error::InvalidRequest { status_code: 400, .. }));
+}
+
+#[tokio::test]
+async fn test_default_policy_makes_single_attempt() {
+    let (mut server, client) = setup(RetryPolicy::default()).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(502)
+        .expect(1)
+        .create();
+
+    let result = client.get_filters("encar").await;
+
+    mock.assert();
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_custom_retry_statuses() {
+    let policy = RetryPolicy {
+        retry_statuses: vec![409],
+        ..fast_policy(2)
+    };
+    let (mut server, client) = setup(policy).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(409)
+        .expect(2)
+        .create();
+
+    let _ = client.get_filters("encar").await;
+
+    mock.assert();
+}
+
+// ── Retry-After ─────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_respects_retry_after_header() {
+    let (mut server, client) = setup(fast_policy(2)).await;
+    let throttled = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(429)
+        .with_header("retry-after", "1")
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body("{}")
+        .expect(1)
+        .create();
+
+    let started = Instant::now();
+    client.get_filters("encar").await.unwrap();
+
+    throttled.assert();
+    ok.assert();
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_long_retry_after_fails_fast() {
+    let (mut server, client) = setup(fast_policy(3)).await;
+    let throttled = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(429)
+        .with_header("retry-after", "86400")
+        .expect(1)
+        .create();
+
+    let started = Instant::now();
+    let result = client.get_filters("encar").await;
+
+    throttled.assert();
+    assert!(started.elapsed() < Duration::from_secs(5));
+    match result {
+        Err(Error::RateLimited { retry_after, .. }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(86400)))
+        }
+        other => panic!("expected RateLimited, got {:?}", other),
+    }
+}
+
+// ── POST and per-call overrides ─────────────────────────────────
+
+#[tokio::test]
+async fn test_retries_get_offer_by_url() {
+    let (mut server, client) = setup(fast_policy(2)).await;
+    let failing = server
+        .mock("POST", "/api/v1/offer/info")
+        .with_status(500)
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("POST", "/api/v1/offer/info")
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_body(r#"{"mark":"BMW"}"#)
+        .expect(1)
+        .create();
+
+    let result = client
+        .get_offer_by_url("https://example.com/car/123")
+        .await
+        .unwrap();
+
+    failing.assert();
+    ok.assert();
+    assert_eq!(result["mark"], "BMW");
+}
+
+#[tokio::test]
+async fn test_with_retry_policy_overrides_single_call() {
+    let (mut server, client) = setup(fast_policy(3)).await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(502)
+        .expect(1)
+        .create();
+
+    let result = client
+        .with_retry_policy(RetryPolicy::none())
+        .get_filters("encar")
+        .await;
+
+    mock.assert();
+    assert!(result.is_err());
+}
+
+// ── Network errors ──────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_connection_error_is_retried_then_returned() {
+    let client = Client::builder("test-key")
+        .base_url("http://127.0.0.1:1")
+        .retry_policy(fast_policy(3))
+        .build()
+        .unwrap();
+
+    let result = client.get_filters("encar").await;
+
+    assert!(matches!(result.unwrap_err(), Error::Network(_)));
+}
+
+/// Serves one raw response per connection, in order, then closes it.
+/// An empty response closes the connection without answering.
+fn raw_server(responses: Vec<&'static str>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    url
+}
+
+const OK: &str = "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}";
+const TRUNCATED: &str = "HTTP/1.1 200 OK\r\ncontent-length: 100\r\nconnection: close\r\n\r\n{\"brands\"";
+
+#[tokio::test]
+async fn test_dropped_request_and_body_are_retried() {
+    let url = raw_server(vec!["", TRUNCATED, OK]);
+    let client = Client::builder("test-key")
+        .base_url(&url)
+        .retry_policy(fast_policy(3))
+        .build()
+        .unwrap();
+
+    let result = client.get_filters("encar").await;
+
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[tokio::test]
+async fn test_network_retry_flags_are_per_kind() {
+    let url = raw_server(vec!["", OK]);
+    let client = Client::builder("test-key")
+        .base_url(&url)
+        .retry_policy(RetryPolicy {
+            retry_on_request: false,
+            ..fast_policy(3)
+        })
+        .build()
+        .unwrap();
+
+    let err = client.get_filters("encar").await.unwrap_err();
+
+    assert!(matches!(err, Error::Network(_)));
+    // The policy opted out, but the error itself is still worth retrying.
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_dropped_body_is_not_retried_when_disabled() {
+    let url = raw_server(vec![TRUNCATED, OK]);
+    let client = Client::builder("test-key")
+        .base_url(&url)
+        .retry_policy(RetryPolicy {
+            retry_on_body: false,
+            ..fast_policy(3)
+        })
+        .build()
+        .unwrap();
+
+    let err = client.get_filters("encar").await.unwrap_err();
+
+    assert!(matches!(err, Error::Network(_)));
+    assert!(err.is_retryable());
+}
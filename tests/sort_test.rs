@@ -0,0 +1,294 @@
+use auto_api_client::{Error, OffersParams, SortField, SortOrder};
+use futures_util::StreamExt;
+use mockito::{Matcher, Mock, ServerGuard};
+
+mod common;
+
+use common::setup_with;
+use serde_json::json;
+
+fn offers_body(prices: &[&str], next_page: i32) -> String {
+    let result: Vec<_> = prices
+        .iter()
+        .enumerate()
+        .map(|(i, price)| {
+            json!({
+                "id": i,
+                "inner_id": format!("p{}", price),
+                "change_type": "added",
+                "created_at": "2024-01-15",
+                "data": {"price": price},
+            })
+        })
+        .collect();
+    json!({"result": result, "meta": {"page": 1, "next_page": next_page, "limit": 20}}).to_string()
+}
+
+fn offers_mock(server: &mut ServerGuard, query: &str, body: String) -> Mock {
+    server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Exact(query.to_string()))
+        .with_status(200)
+        .with_body(body)
+        .create()
+}
+
+fn ids(offers: &auto_api_client::OffersResponse) -> Vec<&str> {
+    offers.result.iter().map(|o| o.inner_id.as_str()).collect()
+}
+
+// ── query parameters ────────────────────────────────────────────
+
+#[test]
+fn test_sort_options_in_query() {
+    let params = OffersParams::builder()
+        .page(1)
+        .sort(SortField::Mileage, SortOrder::Desc)
+        .limit(50)
+        .build();
+
+    let pairs = params.to_query_pairs();
+
+    assert!(pairs.contains(&("sort".into(), "mileage".into())));
+    assert!(pairs.contains(&("order".into(), "desc".into())));
+    assert!(pairs.contains(&("limit".into(), "50".into())));
+}
+
+// ── server-side sorting ─────────────────────────────────────────
+
+#[tokio::test]
+async fn test_server_sorted_response_is_returned_as_is() {
+    let (mut server, client) = setup_with(|b| b).await;
+    let page1 = offers_mock(
+        &mut server,
+        "page=1&sort=price&order=asc&api_key=test-key",
+        offers_body(&["100", "200", "300"], 2),
+    )
+    .expect(2);
+    // Checked once to confirm the order continues across pages.
+    let page2 = offers_mock(
+        &mut server,
+        "page=2&sort=price&order=asc&api_key=test-key",
+        offers_body(&["300", "400"], 0),
+    );
+    let params = OffersParams::builder().page(1).sort(SortField::Price, SortOrder::Asc).build();
+
+    let offers = client.get_offers("encar", &params).await.unwrap();
+    client.get_offers("encar", &params).await.unwrap();
+
+    page1.assert();
+    page2.assert();
+    assert!(!offers.sorted_locally);
+    assert_eq!(offers.meta.next_page, 2);
+}
+
+#[tokio::test]
+async fn test_single_page_result_is_trusted() {
+    let (mut server, client) = setup_with(|b| b).await;
+    let mock = offers_mock(
+        &mut server,
+        "page=1&sort=price&order=asc&api_key=test-key",
+        offers_body(&["100", "200"], 0),
+    );
+    let params = OffersParams::builder().page(1).sort(SortField::Price, SortOrder::Asc).build();
+
+    let offers = client.get_offers("encar", &params).await.unwrap();
+
+    mock.assert();
+    assert!(!offers.sorted_locally);
+}
+
+// ── local fallback ──────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_unsorted_response_falls_back_to_local_sort() {
+    let (mut server, client) = setup_with(|b| b).await;
+    let sorted = offers_mock(
+        &mut server,
+        "page=1&sort=price&order=desc&limit=2&api_key=test-key",
+        offers_body(&["200", "500"], 2),
+    );
+    let page1 = offers_mock(&mut server, "page=1&api_key=test-key", offers_body(&["200", "500"], 2))
+        .expect(2);
+    let page2 = offers_mock(&mut server, "page=2&api_key=test-key", offers_body(&["300", "100"], 0))
+        .expect(2);
+    let params = OffersParams::builder()
+        .page(1)
+        .sort(SortField::Price, SortOrder::Desc)
+        .limit(2)
+        .build();
+
+    let first = client.get_offers("encar", &params).await.unwrap();
+    // The ordering is now known to be unsupported, so the server isn't asked again.
+    let second = client
+        .get_offers("encar", &OffersParams { page: 2, ..params })
+        .await
+        .unwrap();
+
+    sorted.assert();
+    page1.assert();
+    page2.assert();
+    assert!(first.sorted_locally);
+    assert_eq!(ids(&first), vec!["p500", "p300"]);
+    assert_eq!((first.meta.page, first.meta.next_page, first.meta.limit), (1, 2, 2));
+    assert_eq!(ids(&second), vec!["p200", "p100"]);
+    assert_eq!(second.meta.next_page, 0);
+}
+
+#[tokio::test]
+async fn test_page_ordered_by_chance_falls_back_to_local_sort() {
+    let (mut server, client) = setup_with(|b| b).await;
+    // Each page is in order on its own (one has a single item), but page 2
+    // doesn't continue page 1.
+    offers_mock(
+        &mut server,
+        "page=1&sort=price&order=desc&limit=1&api_key=test-key",
+        offers_body(&["200"], 2),
+    );
+    offers_mock(
+        &mut server,
+        "page=2&sort=price&order=desc&limit=1&api_key=test-key",
+        offers_body(&["500"], 0),
+    );
+    offers_mock(&mut server, "page=1&api_key=test-key", offers_body(&["200", "500"], 0));
+    let params = OffersParams::builder()
+        .page(1)
+        .sort(SortField::Price, SortOrder::Desc)
+        .limit(1)
+        .build();
+
+    let offers = client.get_offers("encar", &params).await.unwrap();
+
+    assert!(offers.sorted_locally);
+    assert_eq!(ids(&offers), vec!["p500"]);
+    assert_eq!(offers.meta.next_page, 2);
+}
+
+#[tokio::test]
+async fn test_unparseable_keys_do_not_hide_unsorted_response() {
+    let (mut server, client) = setup_with(|b| b).await;
+    let sorted = offers_mock(
+        &mut server,
+        "page=1&sort=price&order=asc&api_key=test-key",
+        offers_body(&["on request", "300", "100"], 0),
+    );
+    let page1 = offers_mock(
+        &mut server,
+        "page=1&api_key=test-key",
+        offers_body(&["on request", "300", "100"], 0),
+    )
+    .expect(2);
+    let params = OffersParams::builder().page(1).sort(SortField::Price, SortOrder::Asc).build();
+
+    let first = client.get_offers("encar", &params).await.unwrap();
+    let second = client.get_offers("encar", &params).await.unwrap();
+
+    sorted.assert();
+    page1.assert();
+    assert!(first.sorted_locally);
+    assert_eq!(ids(&first), vec!["p100", "p300", "pon request"]);
+    assert!(second.sorted_locally);
+}
+
+#[tokio::test]
+async fn test_too_few_keys_are_not_cached_as_server_sorted() {
+    let (mut server, client) = setup_with(|b| b).await;
+    let page1 = offers_mock(
+        &mut server,
+        "page=1&sort=price&order=asc&api_key=test-key",
+        offers_body(&["on request", "200"], 2),
+    )
+    .expect(2);
+    let page2 = offers_mock(
+        &mut server,
+        "page=2&sort=price&order=asc&api_key=test-key",
+        offers_body(&["on request"], 0),
+    )
+    .expect(2);
+    let params = OffersParams::builder().page(1).sort(SortField::Price, SortOrder::Asc).build();
+
+    let first = client.get_offers("encar", &params).await.unwrap();
+    // Nothing was compared, so the ordering is checked again.
+    client.get_offers("encar", &params).await.unwrap();
+
+    page1.assert();
+    page2.assert();
+    assert!(!first.sorted_locally);
+}
+
+#[tokio::test]
+async fn test_stream_fetches_locally_sorted_set_once() {
+    let (mut server, client) = setup_with(|b| b.local_sort("encar", SortField::Price)).await;
+    let page1 = offers_mock(&mut server, "page=1&api_key=test-key", offers_body(&["200", "500"], 2));
+    let page2 = offers_mock(&mut server, "page=2&api_key=test-key", offers_body(&["300", "100"], 3));
+    let page3 = offers_mock(&mut server, "page=3&api_key=test-key", offers_body(&["400"], 0));
+    let params = OffersParams::builder()
+        .page(1)
+        .sort(SortField::Price, SortOrder::Asc)
+        .limit(2)
+        .build();
+
+    let offers: Vec<_> = client
+        .offers_stream("encar", &params)
+        .map(|item| item.unwrap().inner_id)
+        .collect()
+        .await;
+
+    page1.assert();
+    page2.assert();
+    page3.assert();
+    assert_eq!(offers, vec!["p100", "p200", "p300", "p400", "p500"]);
+}
+
+#[tokio::test]
+async fn test_stream_max_pages_limits_locally_sorted_set() {
+    let (mut server, client) = setup_with(|b| b.local_sort("encar", SortField::Price)).await;
+    offers_mock(&mut server, "page=1&api_key=test-key", offers_body(&["200", "500"], 2));
+    offers_mock(&mut server, "page=2&api_key=test-key", offers_body(&["300", "100"], 0));
+    let params = OffersParams::builder()
+        .page(1)
+        .sort(SortField::Price, SortOrder::Asc)
+        .limit(1)
+        .build();
+
+    let offers: Vec<_> = client
+        .offers_stream("encar", &params)
+        .max_pages(3)
+        .map(|item| item.unwrap().inner_id)
+        .collect()
+        .await;
+
+    assert_eq!(offers, vec!["p100", "p200", "p300"]);
+}
+
+#[tokio::test]
+async fn test_local_sort_puts_missing_values_last() {
+    let (mut server, client) = setup_with(|b| b.local_sort("encar", SortField::Price)).await;
+    let mock = offers_mock(
+        &mut server,
+        "page=1&api_key=test-key",
+        offers_body(&["3.000", "on request", "1.500"], 0),
+    );
+    let params = OffersParams::builder().page(1).sort(SortField::Price, SortOrder::Asc).build();
+
+    let offers = client.get_offers("encar", &params).await.unwrap();
+
+    mock.assert();
+    assert_eq!(ids(&offers), vec!["p1.500", "p3.000", "pon request"]);
+    assert_eq!(offers.meta.limit, 20);
+}
+
+#[tokio::test]
+async fn test_local_sort_fails_beyond_max_pages() {
+    let (mut server, client) = setup_with(|b| b.local_sort("encar", SortField::Year).local_sort_max_pages(1)).await;
+    let mock = offers_mock(&mut server, "page=1&api_key=test-key", offers_body(&["100"], 2));
+    let params = OffersParams::builder().page(1).sort(SortField::Year, SortOrder::Asc).build();
+
+    let result = client.get_offers("encar", &params).await;
+
+    mock.assert();
+    match result {
+        Err(Error::InvalidParams(errors)) => assert_eq!(errors[0].field, "sort"),
+        other => panic!("expected InvalidParams, got {:?}", other.map(|o| o.result.len())),
+    }
+}
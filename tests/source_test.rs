@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use auto_api_client::{Client, Source};
+
+// ── Parsing and display ─────────────────────────────────────────
+
+#[test]
+fn test_source_parses_known_names() {
+    for source in Source::KNOWN {
+        assert_eq!(source.as_str().parse::<Source>().unwrap(), source);
+    }
+}
+
+#[test]
+fn test_source_parses_aliases_case_insensitively() {
+    assert_eq!(Source::from("mobile_de"), Source::Mobilede);
+    assert_eq!(Source::from("Mobile.de"), Source::Mobilede);
+    assert_eq!(Source::from(" ENCAR "), Source::Encar);
+}
+
+#[test]
+fn test_source_unknown_becomes_other() {
+    let source = Source::from("carsales");
+
+    assert_eq!(source, Source::Other("carsales".into()));
+    assert!(!source.is_known());
+    assert_eq!(source.to_string(), "carsales");
+    assert_eq!(source.region(), None);
+}
+
+#[test]
+fn test_source_display_uses_api_identifier() {
+    assert_eq!(Source::Mobilede.to_string(), "mobilede");
+    assert_eq!(Source::Autoscout24.to_string(), "autoscout24");
+}
+
+#[test]
+fn test_source_serde_round_trip() {
+    let json = serde_json::to_string(&vec![Source::Che168, Source::Other("x".into())]).unwrap();
+    assert_eq!(json, r#"["che168","x"]"#);
+
+    let parsed: Vec<Source> = serde_json::from_str(r#"["dubizzle","mobile_de"]"#).unwrap();
+    assert_eq!(parsed, vec![Source::Dubizzle, Source::Mobilede]);
+}
+
+#[test]
+fn test_source_metadata() {
+    assert_eq!(Source::Encar.region(), Some("South Korea"));
+    assert_eq!(Source::Encar.default_currency(), Some("KRW"));
+    assert_eq!(Source::Mobilede.host(), Some("mobile.de"));
+    assert_eq!(Source::Guazi.default_currency(), Some("CNY"));
+    assert_eq!(Source::Dubicars.default_currency(), Some("AED"));
+}
+
+// ── Client integration ──────────────────────────────────────────
+
+#[tokio::test]
+async fn test_client_accepts_source_enum_and_strings() {
+    let mut server = mockito::Server::new_async().await;
+    let client = Client::builder("test-key")
+        .base_url(&server.url())
+        .filters_ttl(Duration::ZERO)
+        .build()
+        .unwrap();
+    let mock = server
+        .mock("GET", "/api/v2/mobilede/filters")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body("{}")
+        .expect(3)
+        .create();
+
+    client.get_filters(Source::Mobilede).await.unwrap();
+    client.get_filters("mobile.de").await.unwrap();
+    client.get_filters(&Source::Mobilede).await.unwrap();
+
+    mock.assert();
+}
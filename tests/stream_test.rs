@@ -0,0 +1,290 @@
+use std::time::{Duration, Instant};
+
+use auto_api_client::{ChangeCursor, ChangesStart, Error, OffersParams};
+use futures_util::StreamExt;
+use mockito::{Mock, ServerGuard};
+
+mod common;
+
+use common::{changes_mock, setup};
+
+fn page_mock(server: &mut ServerGuard, page: i32, ids: &[&str], next_page: i32) -> Mock {
+    let items: Vec<String> = ids
+        .iter()
+        .map(|id| {
+            format!(
+                r#"{{"id":1,"inner_id":"{}","change_type":"added","created_at":"2024-01-15","data":{{}}}}"#,
+                id
+            )
+        })
+        .collect();
+    let body = format!(
+        r#"{{"result":[{}],"meta":{{"page":{},"next_page":{},"limit":20}}}}"#,
+        items.join(","),
+        page,
+        next_page
+    );
+    server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(mockito::Matcher::UrlEncoded("page".into(), page.to_string()))
+        .with_status(200)
+        .with_body(body)
+        .create()
+}
+
+async fn collect_ids(stream: auto_api_client::OffersStream) -> Vec<String> {
+    stream
+        .map(|item| item.unwrap().inner_id)
+        .collect::<Vec<_>>()
+        .await
+}
+
+// ── offers_stream ───────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_offers_stream_follows_next_page() {
+    let (mut server, client) = setup().await;
+    let page1 = page_mock(&mut server, 1, &["a1", "a2"], 2);
+    let page2 = page_mock(&mut server, 2, &["b1"], 3);
+    let page3 = page_mock(&mut server, 3, &["c1"], 0);
+
+    let ids = collect_ids(client.offers_stream("encar", &OffersParams::default())).await;
+
+    page1.assert();
+    page2.assert();
+    page3.assert();
+    assert_eq!(ids, vec!["a1", "a2", "b1", "c1"]);
+}
+
+#[tokio::test]
+async fn test_offers_stream_keeps_filters_on_every_page() {
+    let (mut server, client) = setup().await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("brand".into(), "BMW".into()),
+            mockito::Matcher::UrlEncoded("page".into(), "5".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"page":5,"next_page":0,"limit":20}}"#)
+        .create();
+
+    let params = OffersParams {
+        page: 5,
+        brand: Some("BMW".into()),
+        ..Default::default()
+    };
+    let ids = collect_ids(client.offers_stream("encar", &params)).await;
+
+    mock.assert();
+    assert!(ids.is_empty());
+}
+
+#[tokio::test]
+async fn test_offers_stream_max_pages() {
+    let (mut server, client) = setup().await;
+    let _page1 = page_mock(&mut server, 1, &["a1"], 2);
+    let _page2 = page_mock(&mut server, 2, &["b1"], 3);
+    let page3 = page_mock(&mut server, 3, &["c1"], 0).expect(0);
+
+    let ids = collect_ids(
+        client
+            .offers_stream("encar", &OffersParams::default())
+            .max_pages(2),
+    )
+    .await;
+
+    page3.assert();
+    assert_eq!(ids, vec!["a1", "b1"]);
+}
+
+#[tokio::test]
+async fn test_offers_stream_max_items() {
+    let (mut server, client) = setup().await;
+    let _page1 = page_mock(&mut server, 1, &["a1", "a2"], 2);
+    let page2 = page_mock(&mut server, 2, &["b1", "b2"], 0).expect(1);
+
+    let ids = collect_ids(
+        client
+            .offers_stream("encar", &OffersParams::default())
+            .max_items(3),
+    )
+    .await;
+
+    page2.assert();
+    assert_eq!(ids, vec!["a1", "a2", "b1"]);
+}
+
+#[tokio::test]
+async fn test_offers_stream_prefetch_yields_same_items() {
+    let (mut server, client) = setup().await;
+    let _page1 = page_mock(&mut server, 1, &["a1", "a2"], 2);
+    let _page2 = page_mock(&mut server, 2, &["b1"], 0);
+
+    let ids = collect_ids(
+        client
+            .offers_stream("encar", &OffersParams::default())
+            .prefetch(true),
+    )
+    .await;
+
+    assert_eq!(ids, vec!["a1", "a2", "b1"]);
+}
+
+#[tokio::test]
+async fn test_offers_stream_yields_error_and_stops() {
+    let (mut server, client) = setup().await;
+    let _page1 = page_mock(&mut server, 1, &["a1"], 2);
+    let _page2 = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(mockito::Matcher::UrlEncoded("page".into(), "2".into()))
+        .with_status(500)
+        .with_body(r#"{"message":"boom"}"#)
+        .create();
+
+    let items: Vec<_> = client
+        .offers_stream("encar", &OffersParams::default())
+        .collect()
+        .await;
+
+    assert_eq!(items.len(), 2);
+    assert!(items[0].is_ok());
+    assert!(matches!(items[1], Err(Error::Api { status_code: 500, .. })));
+}
+
+// ── changes_stream ──────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_changes_stream_follows_next_change_id() {
+    let (mut server, client) = setup().await;
+    let _batch1 = changes_mock(&mut server, 100, &["a1", "a2"], 102);
+    let _batch2 = changes_mock(&mut server, 102, &["b1"], 103);
+    let _batch3 = changes_mock(&mut server, 103, &[], 103);
+
+    let ids: Vec<String> = client
+        .changes_stream("encar", 100)
+        .take(3)
+        .map(|item| item.unwrap().inner_id)
+        .collect()
+        .await;
+
+    assert_eq!(ids, vec!["a1", "a2", "b1"]);
+}
+
+#[tokio::test]
+async fn test_changes_stream_exposes_cursor_after_each_item() {
+    let (mut server, client) = setup().await;
+    let _batch1 = changes_mock(&mut server, 100, &["a1", "a2"], 102);
+    let _batch2 = changes_mock(&mut server, 102, &["b1"], 103);
+
+    let mut stream = client.changes_stream("encar", 100);
+
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(stream.cursor(), Some(ChangeCursor { change_id: 100, offset: 1 }));
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(stream.cursor(), Some(ChangeCursor::new(102)));
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(stream.cursor(), Some(ChangeCursor::new(103)));
+}
+
+#[tokio::test]
+async fn test_changes_stream_resumes_from_cursor_offset() {
+    let (mut server, client) = setup().await;
+    let _batch1 = changes_mock(&mut server, 100, &["a1", "a2", "a3"], 103);
+
+    let mut stream = client.changes_stream("encar", ChangeCursor { change_id: 100, offset: 2 });
+    let item = stream.next().await.unwrap().unwrap();
+
+    assert_eq!(item.inner_id, "a3");
+    assert_eq!(stream.cursor(), Some(ChangeCursor::new(103)));
+}
+
+#[tokio::test]
+async fn test_changes_stream_resolves_start_date() {
+    let (mut server, client) = setup().await;
+    let resolve = server
+        .mock("GET", "/api/v2/encar/change_id")
+        .match_query(mockito::Matcher::UrlEncoded("date".into(), "2025-01-15".into()))
+        .with_status(200)
+        .with_body(r#"{"change_id":500}"#)
+        .create();
+    let _batch = changes_mock(&mut server, 500, &["a1"], 501);
+
+    let mut stream = client.changes_stream("encar", "2025-01-15");
+    assert_eq!(stream.cursor(), None);
+    let item = stream.next().await.unwrap().unwrap();
+
+    resolve.assert();
+    assert_eq!(item.inner_id, "a1");
+    assert_eq!(stream.cursor(), Some(ChangeCursor::new(501)));
+}
+
+#[tokio::test]
+async fn test_changes_stream_polls_again_after_empty_batch() {
+    let (mut server, client) = setup().await;
+    let empty = changes_mock(&mut server, 100, &[], 100).expect(1);
+    let filled = changes_mock(&mut server, 100, &["a1"], 101);
+
+    let started = Instant::now();
+    let item = client
+        .changes_stream("encar", 100)
+        .poll_interval(Duration::from_millis(100))
+        .next()
+        .await
+        .unwrap()
+        .unwrap();
+
+    empty.assert();
+    filled.assert();
+    assert_eq!(item.inner_id, "a1");
+    assert!(started.elapsed() >= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_changes_stream_skips_empty_batch_that_moved_on() {
+    let (mut server, client) = setup().await;
+    let empty = changes_mock(&mut server, 100, &[], 105);
+    let filled = changes_mock(&mut server, 105, &["a1"], 106);
+
+    let started = Instant::now();
+    let item = client
+        .changes_stream("encar", 100)
+        .poll_interval(Duration::from_secs(10))
+        .next()
+        .await
+        .unwrap()
+        .unwrap();
+
+    empty.assert();
+    filled.assert();
+    assert_eq!(item.inner_id, "a1");
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn test_changes_start_from_strings_is_date() {
+    assert_eq!(ChangesStart::from("2025-01-15"), ChangesStart::Date("2025-01-15".into()));
+    assert_eq!(
+        ChangesStart::from(String::from("2025-01-15")),
+        ChangesStart::Date("2025-01-15".into())
+    );
+}
+
+#[tokio::test]
+async fn test_changes_stream_yields_errors_and_keeps_going() {
+    let (mut server, client) = setup().await;
+    let _failing = server
+        .mock("GET", "/api/v2/encar/changes")
+        .match_query(mockito::Matcher::Any)
+        .with_status(500)
+        .expect(1)
+        .create();
+    let _batch = changes_mock(&mut server, 100, &["a1"], 101);
+
+    let mut stream = client
+        .changes_stream("encar", 100)
+        .poll_interval(Duration::from_millis(10));
+
+    assert!(matches!(stream.next().await, Some(Err(Error::Api { status_code: 500, .. }))));
+    assert_eq!(stream.next().await.unwrap().unwrap().inner_id, "a1");
+}
@@ -0,0 +1,234 @@
+#![cfg(feature = "tower")]
+
+use std::convert::Infallible;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use auto_api_client::{
+    Client, Error, GetChangeId, GetChanges, GetFilters, GetOffer, GetOfferByUrl, GetOffers,
+    OffersParams, OffersResponse, ReqwestService,
+};
+use mockito::Matcher;
+use tower::{service_fn, ServiceBuilder, ServiceExt};
+
+const OFFERS: &str = r#"{"result":[],"meta":{"page":2,"next_page":0,"limit":20}}"#;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Wraps an error the way `tower::buffer`'s `ServiceError` does.
+#[derive(Debug)]
+struct Wrapped(BoxError);
+
+impl fmt::Display for Wrapped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffered service failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for Wrapped {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.0.as_ref())
+    }
+}
+
+fn response(status: u16, body: &str) -> http::Response<Vec<u8>> {
+    let mut response = http::Response::new(body.as_bytes().to_vec());
+    *response.status_mut() = http::StatusCode::from_u16(status).unwrap();
+    response
+}
+
+// ── tower transport ─────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_client_backed_by_tower_layers() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+        .match_header("x-layer", "yes")
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+    let transport = ServiceBuilder::new()
+        .map_request(|mut request: http::Request<Vec<u8>>| {
+            request
+                .headers_mut()
+                .insert("x-layer", http::HeaderValue::from_static("yes"));
+            request
+        })
+        .service(ReqwestService::default());
+    let client = Client::builder("test-key")
+        .base_url(&server.url())
+        .tower_service(transport)
+        .build()
+        .unwrap();
+
+    let offers = client
+        .get_offers("encar", &OffersParams { page: 2, ..Default::default() })
+        .await
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(offers.meta.page, 2);
+}
+
+#[tokio::test]
+async fn test_tower_transport_sees_requests() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let transport = service_fn(move |request: http::Request<Vec<u8>>| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let body = String::from_utf8(request.body().clone()).unwrap();
+        async move {
+            assert_eq!(request.method(), http::Method::POST);
+            assert_eq!(request.headers()["x-api-key"], "test-key");
+            assert_eq!(body, r#"{"url":"https://encar.com/1"}"#);
+            Ok::<_, Infallible>(response(200, r#"{"id":1}"#))
+        }
+    });
+    let client = Client::builder("test-key").tower_service(transport).build().unwrap();
+
+    let offer = client.get_offer_by_url("https://encar.com/1").await.unwrap();
+
+    assert_eq!(offer["id"], 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_tower_transport_errors() {
+    let status = service_fn(|_: http::Request<Vec<u8>>| async {
+        Ok::<_, Infallible>(response(404, r#"{"message":"no such source"}"#))
+    });
+    let failing = service_fn(|_: http::Request<Vec<u8>>| async {
+        Err::<http::Response<Vec<u8>>, _>(std::io::Error::other("overloaded"))
+    });
+
+    let not_found = Client::builder("test-key")
+        .tower_service(status)
+        .build()
+        .unwrap()
+        .get_filters("nowhere")
+        .await;
+    let transport = Client::builder("test-key")
+        .tower_service(failing)
+        .build()
+        .unwrap()
+        .get_filters("encar")
+        .await;
+
+    assert!(matches!(not_found, Err(Error::NotFound { .. })));
+    match transport {
+        Err(Error::Transport(e)) => assert_eq!(e.to_string(), "overloaded"),
+        other => panic!("expected Transport, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_wrapped_transport_error_hides_api_key() {
+    let transport = ServiceBuilder::new()
+        .map_err(|e: reqwest::Error| Wrapped(e.into()))
+        .service(ReqwestService::default());
+    let client = Client::builder("secret-key-123")
+        .base_url("http://127.0.0.1:1")
+        .tower_service(transport)
+        .build()
+        .unwrap();
+
+    let err = client.get_filters("encar").await.unwrap_err();
+
+    assert!(matches!(err, Error::Transport(_)), "got {:?}", err);
+    assert!(!err.to_string().contains("secret-key-123"), "{}", err);
+    assert!(!format!("{:?}", err).contains("secret-key-123"));
+}
+
+#[tokio::test]
+async fn test_tower_timeout_is_timeout_error() {
+    let slow = service_fn(|_: http::Request<Vec<u8>>| async {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Ok::<_, Infallible>(response(200, "{}"))
+    });
+    let timeout = ServiceBuilder::new()
+        .timeout(Duration::from_millis(10))
+        .service(slow);
+    let wrapped = ServiceBuilder::new()
+        .map_err(Wrapped)
+        .timeout(Duration::from_millis(10))
+        .service(slow);
+
+    for result in [
+        Client::builder("test-key").tower_service(timeout).build().unwrap().get_filters("encar").await,
+        Client::builder("test-key").tower_service(wrapped).build().unwrap().get_filters("encar").await,
+    ] {
+        let err = result.unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)), "got {:?}", err);
+        assert!(err.is_retryable());
+    }
+}
+
+// ── endpoint services ───────────────────────────────────────────
+
+#[tokio::test]
+async fn test_endpoint_services() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(OFFERS)
+        .create();
+    server
+        .mock("GET", "/api/v2/encar/changes")
+        .match_query(Matcher::UrlEncoded("change_id".into(), "7".into()))
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"cur_change_id":7,"next_change_id":7,"limit":20}}"#)
+        .create();
+    server
+        .mock("POST", "/api/v1/offer/info")
+        .with_status(200)
+        .with_body(r#"{"id":1}"#)
+        .create();
+    server
+        .mock("GET", "/api/v2/encar/filters")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"brands":["BMW"]}"#)
+        .create();
+    server
+        .mock("GET", "/api/v2/encar/offer")
+        .match_query(Matcher::UrlEncoded("inner_id".into(), "abc".into()))
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"page":1,"next_page":0,"limit":1}}"#)
+        .create();
+    server
+        .mock("GET", "/api/v2/encar/change_id")
+        .match_query(Matcher::UrlEncoded("date".into(), "2025-01-15".into()))
+        .with_status(200)
+        .with_body(r#"{"change_id":42}"#)
+        .create();
+    let client = Client::builder("test-key").base_url(&server.url()).build().unwrap();
+
+    let page = ServiceBuilder::new()
+        .map_response(|offers: OffersResponse| offers.meta.page)
+        .service(client.clone())
+        .oneshot(GetOffers::new("encar", OffersParams::default()))
+        .await
+        .unwrap();
+    let changes = client.clone().oneshot(GetChanges::new("encar", 7)).await.unwrap();
+    let offer = client
+        .clone()
+        .oneshot(GetOfferByUrl::new("https://encar.com/1"))
+        .await
+        .unwrap();
+    let filters = client.clone().oneshot(GetFilters::new("encar")).await.unwrap();
+    let single = client.clone().oneshot(GetOffer::new("encar", "abc")).await.unwrap();
+    let change_id = client.oneshot(GetChangeId::new("encar", "2025-01-15")).await.unwrap();
+
+    assert_eq!(filters["brands"][0], "BMW");
+    assert_eq!(single.meta.limit, 1);
+    assert_eq!(change_id, 42);
+    assert_eq!(page, 2);
+    assert_eq!(changes.meta.cur_change_id, 7);
+    assert_eq!(offer["id"], 1);
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "tracing")]
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use auto_api_client::{Client, OffersParams, RetryPolicy};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+type Recorded = (Id, &'static str, HashMap<String, String>);
+
+/// Fields recorded per span, in creation order.
+#[derive(Clone, Default)]
+struct Spans(Arc<Mutex<Vec<Recorded>>>);
+
+impl Spans {
+    fn named(&self, name: &str) -> Vec<HashMap<String, String>> {
+        let spans = self.0.lock().unwrap();
+        spans
+            .iter()
+            .filter(|(_, n, _)| *n == name)
+            .map(|(_, _, fields)| fields.clone())
+            .collect()
+    }
+}
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Spans {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let mut fields = HashMap::new();
+        attrs.record(&mut Fields(&mut fields));
+        self.0.lock().unwrap().push((id.clone(), attrs.metadata().name(), fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut spans = self.0.lock().unwrap();
+        if let Some((_, _, fields)) = spans.iter_mut().rev().find(|(i, _, _)| i == id) {
+            values.record(&mut Fields(fields));
+        }
+    }
+}
+
+async fn setup() -> (mockito::ServerGuard, Client, Spans, tracing::subscriber::DefaultGuard) {
+    let spans = Spans::default();
+    let guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(spans.clone()));
+    let server = mockito::Server::new_async().await;
+    let client = Client::builder("secret-key-123")
+        .base_url(&server.url())
+        .retry_policy(RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::from_millis(1),
+            jitter: false,
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+    (server, client, spans, guard)
+}
+
+// ── request spans ───────────────────────────────────────────────
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_request_span_records_fields() {
+    let (mut server, client, spans, _guard) = setup().await;
+    let body = r#"{"result":[],"meta":{"page":3,"next_page":0,"limit":20}}"#;
+    let failing = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(mockito::Matcher::Any)
+        .with_status(503)
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/api/v2/encar/offers")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(body)
+        .create();
+
+    client
+        .get_offers("encar", &OffersParams { page: 3, ..Default::default() })
+        .await
+        .unwrap();
+
+    failing.assert();
+    ok.assert();
+    let requests = spans.named("auto_api.request");
+    assert_eq!(requests.len(), 1);
+    let span = &requests[0];
+    assert_eq!(span["source"], "encar");
+    assert_eq!(span["endpoint"], "offers");
+    assert_eq!(span["page"], "3");
+    assert_eq!(span["status"], "200");
+    assert_eq!(span["retries"], "1");
+    assert_eq!(span["response_bytes"], body.len().to_string());
+    assert!(span.contains_key("latency_ms"));
+    assert_eq!(spans.named("auto_api.handle_response").len(), 1);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_changes_span_records_change_id() {
+    let (mut server, client, spans, _guard) = setup().await;
+    let mock = server
+        .mock("GET", "/api/v2/encar/changes")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"result":[],"meta":{"cur_change_id":42,"next_change_id":42,"limit":20}}"#)
+        .create();
+
+    client.get_changes("encar", 42).await.unwrap();
+
+    mock.assert();
+    let span = &spans.named("auto_api.request")[0];
+    assert_eq!(span["endpoint"], "changes");
+    assert_eq!(span["change_id"], "42");
+    assert!(!span.contains_key("page"));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_spans_never_contain_api_key() {
+    let (mut server, client, spans, _guard) = setup().await;
+    let mock = server
+        .mock("POST", "/api/v1/offer/info")
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    client.get_offer_by_url("https://encar.com/1").await.unwrap();
+
+    mock.assert();
+    let all = spans.0.lock().unwrap();
+    assert!(!all.is_empty());
+    for (_, _, fields) in all.iter() {
+        assert!(fields.values().all(|v| !v.contains("secret-key-123")));
+    }
+    let request = all.iter().find(|(_, n, _)| *n == "auto_api.request").unwrap();
+    assert_eq!(request.2["endpoint"], "offer/info");
+}
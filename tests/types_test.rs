@@ -0,0 +1,137 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use auto_api_client::{ChangeItem, ChangeType, OfferItem};
+use serde_json::json;
+
+fn change(change_type: &str, created_at: &str) -> ChangeItem {
+    serde_json::from_value(json!({
+        "id": 1,
+        "inner_id": "a1",
+        "change_type": change_type,
+        "created_at": created_at,
+        "data": {},
+    }))
+    .unwrap()
+}
+
+// ── ChangeType ──────────────────────────────────────────────────
+
+#[test]
+fn test_change_type_deserializes_known_values() {
+    assert_eq!(change("added", "").change_type, ChangeType::Added);
+    assert_eq!(change("changed", "").change_type, ChangeType::Changed);
+    assert_eq!(change("removed", "").change_type, ChangeType::Removed);
+}
+
+#[test]
+fn test_change_type_keeps_unknown_values() {
+    let item = change("archived", "");
+
+    assert_eq!(item.change_type, ChangeType::Unknown("archived".into()));
+    assert_eq!(item.change_type.to_string(), "archived");
+    assert_eq!(change("", "").change_type, ChangeType::Unknown(String::new()));
+}
+
+#[test]
+fn test_change_type_serializes_as_string() {
+    assert_eq!(serde_json::to_string(&ChangeType::Removed).unwrap(), r#""removed""#);
+}
+
+#[test]
+fn test_change_item_predicates() {
+    assert!(change("added", "").is_added());
+    assert!(change("changed", "").is_changed());
+    assert!(change("removed", "").is_removed());
+    assert!(!change("removed", "").is_added());
+}
+
+// ── created_at ──────────────────────────────────────────────────
+
+#[test]
+fn test_created_time_parses_date_and_date_time() {
+    assert_eq!(
+        change("added", "2024-01-15").created_time(),
+        Some(UNIX_EPOCH + Duration::from_secs(1_705_276_800))
+    );
+    assert_eq!(
+        change("added", "2024-01-15 00:00:30").created_time(),
+        Some(UNIX_EPOCH + Duration::from_secs(1_705_276_830))
+    );
+    assert_eq!(change("added", "").created_time(), None);
+}
+
+#[test]
+fn test_created_time_parses_unix_timestamps() {
+    assert_eq!(
+        change("added", "1705276800").created_time(),
+        Some(UNIX_EPOCH + Duration::from_secs(1_705_276_800))
+    );
+    assert_eq!(
+        change("added", "1705276800500").created_time(),
+        Some(UNIX_EPOCH + Duration::from_millis(1_705_276_800_500))
+    );
+}
+
+#[test]
+fn test_created_time_out_of_range_is_none() {
+    assert_eq!(change("added", "18446744073709551615").created_time(), None);
+    assert_eq!(change("added", "99999999999999999999").created_time(), None);
+}
+
+#[test]
+fn test_created_time_parses_utc_offsets() {
+    // 2024-01-01 04:30 UTC
+    let expected = Some(UNIX_EPOCH + Duration::from_secs(1_704_083_400));
+    assert_eq!(change("added", "2024-01-01T10:00:00+05:30").created_time(), expected);
+    assert_eq!(change("added", "2024-01-01T10:00:00+0530").created_time(), expected);
+    assert_eq!(change("added", "2024-01-01T02:30:00-02").created_time(), expected);
+}
+
+#[test]
+fn test_created_time_rejects_invalid_offsets() {
+    for value in [
+        "2024-01-01T00:00:00+9999999999999999",
+        "2024-01-01T00:00:00+24:00",
+        "2024-01-01T00:00:00+05:60",
+        "2024-01-01T00:00:00+5",
+        "2024-01-01T00:00:00+05:3",
+        "2024-01-01T00:00:00+0a30",
+        "2024-01-01T00:00:00Zfoo",
+    ] {
+        assert_eq!(change("added", value).created_time(), None, "{}", value);
+    }
+}
+
+#[test]
+fn test_created_time_rejects_days_past_month_end() {
+    assert_eq!(change("added", "2024-02-31").created_time(), None);
+    assert_eq!(change("added", "2023-02-29").created_time(), None);
+    assert_eq!(change("added", "2024-04-31 10:00:00").created_time(), None);
+    assert_eq!(
+        change("added", "2024-02-29").created_time(),
+        Some(UNIX_EPOCH + Duration::from_secs(1_709_164_800))
+    );
+}
+
+#[test]
+fn test_offer_item_created_time() {
+    let item: OfferItem = serde_json::from_value(json!({
+        "id": 1, "inner_id": "a1", "change_type": "added",
+        "created_at": "2024-01-15T00:00:00Z", "data": {},
+    }))
+    .unwrap();
+
+    assert_eq!(item.change_type, ChangeType::Added);
+    assert_eq!(item.created_time(), Some(UNIX_EPOCH + Duration::from_secs(1_705_276_800)));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_created_at_utc_with_chrono() {
+    use chrono::{TimeZone, Utc};
+
+    assert_eq!(
+        change("added", "2024-01-15 10:30:00").created_at_utc(),
+        Some(Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap())
+    );
+}
@@ -0,0 +1,183 @@
+use auto_api_client::{Client, Error, Filters, OffersParams};
+use mockito::{Matcher, Mock, ServerGuard};
+
+const FILTERS: &str = r#"{
+    "brands": {"Mercedes-Benz": {"E-Class": ["W213"]}, "BMW": {"X5": ["G05"]}},
+    "body_types": ["sedan", "suv"],
+    "colors": ["black", "white"]
+}"#;
+
+fn filters() -> Filters {
+    Filters::from_value(serde_json::from_str(FILTERS).unwrap())
+}
+
+fn invalid_params(result: Result<(), Error>) -> Vec<auto_api_client::ParamError> {
+    match result {
+        Err(Error::InvalidParams(errors)) => errors,
+        other => panic!("expected InvalidParams, got {:?}", other),
+    }
+}
+
+async fn setup() -> (ServerGuard, Client) {
+    let server = mockito::Server::new_async().await;
+    let mut client = Client::builder("test-key").strict_params(true).build().unwrap();
+    client.set_base_url(server.url().as_str());
+    (server, client)
+}
+
+fn mock(server: &mut ServerGuard, path: &str, body: &str) -> Mock {
+    server
+        .mock("GET", path)
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(body)
+        .create()
+}
+
+// ── OffersParams::validate ──────────────────────────────────────
+
+#[test]
+fn test_validate_accepts_known_values_ignoring_case() {
+    let params = OffersParams {
+        brand: Some("bmw".into()),
+        model: Some("x5".into()),
+        configuration: Some("G05".into()),
+        body_type: Some("SUV".into()),
+        year_from: Some(2018),
+        year_to: Some(2022),
+        ..Default::default()
+    };
+
+    assert!(params.validate(&filters()).is_ok());
+}
+
+#[test]
+fn test_validate_suggests_brand() {
+    let params = OffersParams {
+        brand: Some("Mercedes".into()),
+        ..Default::default()
+    };
+
+    let errors = invalid_params(params.validate(&filters()));
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].field, "brand");
+    assert_eq!(errors[0].suggestion.as_deref(), Some("Mercedes-Benz"));
+}
+
+#[test]
+fn test_validate_suggests_typo() {
+    let params = OffersParams {
+        brand: Some("BMW".into()),
+        model: Some("X6".into()),
+        color: Some("blakc".into()),
+        ..Default::default()
+    };
+
+    let errors = invalid_params(params.validate(&filters()));
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].field, "model");
+    assert_eq!(errors[0].suggestion.as_deref(), Some("X5"));
+    assert_eq!(errors[1].field, "color");
+    assert_eq!(errors[1].suggestion.as_deref(), Some("black"));
+}
+
+#[test]
+fn test_validate_unknown_body_type_without_suggestion() {
+    let params = OffersParams {
+        body_type: Some("pickup".into()),
+        ..Default::default()
+    };
+
+    let errors = invalid_params(params.validate(&filters()));
+
+    assert_eq!(errors[0].field, "body_type");
+    assert_eq!(errors[0].suggestion, None);
+}
+
+#[test]
+fn test_validate_skips_unlisted_dimensions() {
+    let params = OffersParams {
+        transmission: Some("anything".into()),
+        ..Default::default()
+    };
+
+    assert!(params.validate(&filters()).is_ok());
+}
+
+#[test]
+fn test_validate_rejects_inverted_range() {
+    let params = OffersParams {
+        price_from: Some(50000),
+        price_to: Some(10000),
+        ..Default::default()
+    };
+
+    let errors = invalid_params(params.validate(&filters()));
+
+    assert_eq!(errors[0].field, "price_from");
+    assert!(Error::InvalidParams(errors).to_string().contains("price_from"));
+}
+
+// ── strict mode ─────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_strict_mode_rejects_before_sending() {
+    let (mut server, client) = setup().await;
+    let filters_mock = mock(&mut server, "/api/v2/encar/filters", FILTERS);
+    let offers_mock = mock(&mut server, "/api/v2/encar/offers", "{}").expect(0);
+    let params = OffersParams {
+        brand: Some("Mercedes".into()),
+        ..Default::default()
+    };
+
+    let result = client.get_offers("encar", &params).await;
+
+    assert!(matches!(result, Err(Error::InvalidParams(_))));
+    filters_mock.assert();
+    offers_mock.assert();
+}
+
+#[tokio::test]
+async fn test_strict_mode_caches_filters() {
+    let (mut server, client) = setup().await;
+    let filters_mock = mock(&mut server, "/api/v2/encar/filters", FILTERS).expect(1);
+    let offers_mock = mock(
+        &mut server,
+        "/api/v2/encar/offers",
+        r#"{"result":[],"meta":{"page":1,"next_page":0,"limit":20}}"#,
+    )
+    .expect(2);
+    let params = OffersParams {
+        brand: Some("BMW".into()),
+        ..Default::default()
+    };
+
+    client.get_offers("encar", &params).await.unwrap();
+    client.clone().get_offers("encar", &params).await.unwrap();
+
+    filters_mock.assert();
+    offers_mock.assert();
+}
+
+#[tokio::test]
+async fn test_non_strict_mode_skips_filters() {
+    let (mut server, mut client) = setup().await;
+    client.set_strict_params(false);
+    let filters_mock = mock(&mut server, "/api/v2/encar/filters", FILTERS).expect(0);
+    let offers_mock = mock(
+        &mut server,
+        "/api/v2/encar/offers",
+        r#"{"result":[],"meta":{"page":1,"next_page":0,"limit":20}}"#,
+    );
+    let params = OffersParams {
+        brand: Some("Mercedes".into()),
+        ..Default::default()
+    };
+
+    client.get_offers("encar", &params).await.unwrap();
+
+    filters_mock.assert();
+    offers_mock.assert();
+}